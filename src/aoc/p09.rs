@@ -26,8 +26,7 @@ fn neighbors(g: &Grid, x: usize, y: usize) -> Vec<(usize, usize)> {
 fn flood_fill(g: &Grid, x: usize, y: usize) -> HashSet<(usize, usize)> {
     let mut stack = vec![(x, y)];
     let mut out = HashSet::default();
-    while !stack.is_empty() {
-        let curr = stack.pop().unwrap();
+    while let Some(curr) = stack.pop() {
         if g[curr.0][curr.1] == 9 {
             continue;
         }
@@ -1,16 +1,12 @@
 //! Find the sum of the even-valued Fibonacci numbers leq 4,000,000.
-fn solve_for(bound: u32) -> u32 {
-    let (mut prev, mut curr) = (1, 2);
-    let mut out = 0;
-
-    while curr < bound {
-        out += curr;
-        for _ in 0..3 {
-            (prev, curr) = (curr, prev + curr);
-        }
-    }
+use crate::utils::seqs::Fibonacci;
 
-    out
+fn solve_for(bound: u32) -> u32 {
+    // every third Fibonacci number is even, starting from F(0) = 0
+    Fibonacci::<u32>::new()
+        .step_by(3)
+        .take_while(|&f| f < bound)
+        .sum()
 }
 
 super::example!(100 => 44);
@@ -1,14 +1,15 @@
 //! Find the sum of all the multiples of 3 or 5 below 1000.
 use std::collections::HashMap;
 
-use crate::utils::primes::PrimeFactorization;
+use crate::utils::primes::SpfTable;
 
 fn solve_for(bound: u32) -> u32 {
     // The idea is to get the prime factorizations of each number less than the bound, find the
     // greatest exponent associated with each prime in that range, and multiply those numbers
     // together.
+    let spf = SpfTable::new(bound as usize);
     (1..=bound)
-        .flat_map(PrimeFactorization::of)
+        .flat_map(|n| spf.factorize(n))
         .fold(HashMap::<u32, u32>::default(), |mut hm, p| {
             if let Err(mut e) = hm.try_insert(p.factor, p.exponent) {
                 let v = e.entry.get_mut();
@@ -1,9 +1,10 @@
 //! Find the product of the unique Pythagorean triplet with sum 1000.
+use crate::utils::seqs::PythagoreanTriples;
+
 fn solve_for(sum: u32) -> u32 {
-    let (a, b, c) = (1..=sum)
-        .flat_map(|a| ((a + 1)..=((sum - a) / 2)).map(move |b| (a, b)))
-        .map(|(a, b)| (a, b, sum - a - b))
-        .find(|(a, b, c)| a * a + b * b == c * c)
+    let (a, b, c) = PythagoreanTriples::with_perimeter_at_most(sum)
+        .with_multiples()
+        .find(|(a, b, c)| a + b + c == sum)
         .expect("puzzle has a solution");
 
     a * b * c
@@ -2,7 +2,7 @@
 use crate::utils::primes::Primes;
 
 fn solve_for(bound: u64) -> u64 {
-    Primes::<u64>::new().take_while(|&p| p < bound).sum()
+    Primes::<u64>::up_to(bound - 1).sum()
 }
 
 super::example!(10 => 17);
@@ -1,4 +1,8 @@
 //! Work out the first ten digits of the sum of the following one-hundred 50-digit numbers.
+use num::ToPrimitive;
+
+use crate::utils::bignum;
+
 const NUMBERS: &str = "
 37107287533902102798797998220837590246510135740250
 46376937677490009712648124896970078050417018260538
@@ -103,26 +107,9 @@ const NUMBERS: &str = "
 ";
 
 fn solve_for(first_digits: usize) -> u64 {
-    NUMBERS
-        .trim()
-        .split('\n')
-        .map(|s| -> u64 {
-            s.chars()
-                // technically for sufficiently large lists this is not a correct optimization,
-                // since we're assuming that truncating at 2 extra sig figs is sufficient
-                .take(first_digits + 2)
-
-                .collect::<String>()
-                .parse()
-                .expect("input if valid u64s")
-        })
-        .sum::<u64>()
-        // another optimization that's not quite correct for certain inputs; it assumes that we get
-        // something at least 100x (and no more than 999x, but that's impossible for this size
-        // list) bigger than the max of the original list; then we truncate off two sig figs
-        // because of that and another two because of the extra two we kept for precision earlier.
-        // but that's a pretty safe assumption with a list of size 50.
-        / 10000
+    bignum::first_digits(&bignum::sum_lines(NUMBERS), first_digits)
+        .to_u64()
+        .expect("first digits fit in a u64")
 }
 
 super::example!(2 => 55);
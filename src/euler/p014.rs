@@ -1,16 +1,12 @@
 //! Find the collatz number under 1,000,000 with the longest sequence.
-use std::collections::HashMap;
-
-use crate::utils::seqs::collatz_length;
+use crate::utils::seqs::CollatzTable;
 
 fn solve_for(bound: u64) -> u64 {
+    let table = CollatzTable::new(bound as usize);
+    // n has a shorter sequence than 2n, so the answer is in the upper half
     ((bound / 2)..bound)
-        .scan(HashMap::with_capacity((bound / 2) as usize), |hm, n| {
-            Some((n, collatz_length(n, hm)))
-        })
-        .max_by_key(|&(_, l)| l)
+        .max_by_key(|&n| table.get(n))
         .expect("the iterator is non-empty")
-        .0
 }
 
 super::example!(10 => 9);
@@ -1,11 +1,5 @@
-#![feature(
-    destructuring_assignment,
-    int_log,
-    map_try_insert,
-    step_trait,
-    test,
-    trait_alias
-)]
+#![feature(map_try_insert, step_trait)]
+#![cfg_attr(test, feature(test))]
 
 pub mod aoc;
 pub mod euler;
@@ -1,5 +1,12 @@
 //! Contains utilities for Project Euler solutions.
 
+pub mod bignum;
+pub mod cfrac;
+pub mod digits;
+pub mod dirichlet;
+pub mod modular;
 mod num_fn;
+pub mod number_theory;
+pub mod partitions;
 pub mod primes;
 pub mod seqs;
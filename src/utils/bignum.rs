@@ -0,0 +1,95 @@
+//! Exact arithmetic on huge numbers, on top of `num::BigUint`.
+
+use num::{BigUint, Zero};
+
+/// The number of decimal digits of n; zero has one digit.
+///
+/// # Examples
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::bignum::num_digits;
+/// assert_eq!(num_digits(&BigUint::from(0_u32)), 1);
+/// assert_eq!(num_digits(&BigUint::from(999_u32)), 3);
+/// assert_eq!(num_digits(&BigUint::from(1000_u32)), 4);
+/// assert_eq!(num_digits(&BigUint::from(2_u32).pow(1000)), 302);
+/// ```
+#[must_use]
+pub fn num_digits(n: &BigUint) -> usize {
+    n.to_radix_le(10).len()
+}
+
+/// The first (most significant) k decimal digits of n, or all of n if it has at most k digits.
+///
+/// # Examples
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::bignum::first_digits;
+/// assert_eq!(first_digits(&BigUint::from(123_456_u32), 2), BigUint::from(12_u32));
+/// assert_eq!(first_digits(&BigUint::from(123_456_u32), 10), BigUint::from(123_456_u32));
+/// assert_eq!(first_digits(&BigUint::from(2_u32).pow(1000), 5), BigUint::from(10_715_u32));
+/// ```
+#[must_use]
+pub fn first_digits(n: &BigUint, k: usize) -> BigUint {
+    let digits = num_digits(n);
+    if digits <= k {
+        n.clone()
+    } else {
+        n / BigUint::from(10_u32).pow((digits - k) as u32)
+    }
+}
+
+/// The last (least significant) k decimal digits of n, i.e. n mod 10^k.
+///
+/// # Examples
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::bignum::last_digits;
+/// assert_eq!(last_digits(&BigUint::from(123_456_u32), 2), BigUint::from(56_u32));
+/// assert_eq!(last_digits(&BigUint::from(2_u32).pow(1000), 5), BigUint::from(69_376_u32));
+/// ```
+#[must_use]
+pub fn last_digits(n: &BigUint, k: usize) -> BigUint {
+    n % BigUint::from(10_u32).pow(k as u32)
+}
+
+/// The sum of the decimal digits of n.
+///
+/// # Examples
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::bignum::digit_sum;
+/// assert_eq!(digit_sum(&BigUint::from(2_u32).pow(1000)), 1366);
+/// assert_eq!(digit_sum(&(1..=100_u32).map(BigUint::from).product()), 648);
+/// ```
+#[must_use]
+pub fn digit_sum(n: &BigUint) -> u64 {
+    n.to_radix_le(10).into_iter().map(u64::from).sum()
+}
+
+/// Parse a list of decimal numbers, one per line, and sum them exactly.
+///
+/// # Panics
+/// Panics if any line is not a decimal number.
+///
+/// # Examples
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::bignum::sum_lines;
+/// let sum = sum_lines("
+/// 99999999999999999999
+/// 1
+/// ");
+/// assert_eq!(sum, BigUint::from(10_u32).pow(20));
+/// ```
+#[must_use]
+pub fn sum_lines(lines: &str) -> BigUint {
+    lines
+        .trim()
+        .lines()
+        .map(|s| {
+            s.trim()
+                .parse::<BigUint>()
+                .expect("input is valid integers")
+        })
+        .fold(BigUint::zero(), |sum, n| sum + n)
+}
@@ -0,0 +1,310 @@
+//! Continued fractions, their convergents, and Pell's equation.
+//!
+//! A continued fraction `[a0; a1, a2, ...]` stands for `a0 + 1/(a1 + 1/(a2 + ...))`. Its
+//! convergents `h(n)/k(n)`, the values of the truncations `[a0; a1, ..., an]`, satisfy
+//!
+//! ```text
+//! h(n) = a(n) h(n-1) + h(n-2)    h(-1) = 1, h(-2) = 0
+//! k(n) = a(n) k(n-1) + k(n-2)    k(-1) = 0, k(-2) = 1
+//! ```
+//!
+//! and are already in lowest terms. Their numerators and denominators grow exponentially, so
+//! they're kept as `BigUint`s.
+
+use num::rational::Ratio;
+use num::{BigUint, One, Zero};
+
+/// The continued fraction of a square root, `[a0; (a1, ..., ar)]`, which is periodic from the
+/// first term on, and whose period ends in `2 a0`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::cfrac::SqrtExpansion;
+/// let sqrt_23 = SqrtExpansion::of(23);
+/// assert_eq!(sqrt_23.a0, 4);
+/// assert_eq!(sqrt_23.period, [1, 3, 1, 8]);
+/// assert_eq!(sqrt_23.terms().take(7).collect::<Vec<_>>(), [4, 1, 3, 1, 8, 1, 3]);
+///
+/// // perfect squares have no period
+/// assert!(SqrtExpansion::of(16).period.is_empty());
+/// assert_eq!(SqrtExpansion::of(16).terms().collect::<Vec<_>>(), [4]);
+/// ```
+/// ```
+/// # use pj_euler::utils::cfrac::SqrtExpansion;
+/// // the number of square roots up to 13 with an odd period
+/// assert_eq!((2..=13).filter(|&n| SqrtExpansion::of(n).period.len() % 2 == 1).count(), 4);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SqrtExpansion {
+    /// The integer part of the square root.
+    pub a0: u64,
+
+    /// The repeating terms, or nothing for perfect squares.
+    pub period: Vec<u64>,
+}
+
+impl SqrtExpansion {
+    /// The continued fraction of the square root of n.
+    ///
+    /// Each complete quotient has the form `(sqrt(n) + m) / d`, and the next term is its integer
+    /// part; the quotients, and so the terms, repeat once a term is `2 a0`.
+    #[must_use]
+    pub fn of(n: u64) -> Self {
+        let a0 = n.isqrt();
+        let mut period = vec![];
+        if a0 * a0 != n {
+            let (mut m, mut d, mut a) = (0, 1, a0);
+            while a != 2 * a0 {
+                m = d * a - m;
+                d = (n - m * m) / d;
+                a = (a0 + m) / d;
+                period.push(a);
+            }
+        }
+        Self { a0, period }
+    }
+
+    /// An iterator over all the terms, starting with `a0` and then cycling through the period.
+    pub fn terms(&self) -> impl Iterator<Item = u64> {
+        std::iter::once(self.a0).chain(self.period.clone().into_iter().cycle())
+    }
+}
+
+/// An iterator over the terms of the continued fraction of e, `[2; 1, 2, 1, 1, 4, 1, 1, 6, ...]`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::cfrac::e_terms;
+/// assert_eq!(e_terms().take(10).collect::<Vec<_>>(), [2, 1, 2, 1, 1, 4, 1, 1, 6, 1]);
+/// ```
+pub fn e_terms() -> impl Iterator<Item = u64> {
+    std::iter::once(2).chain((1..).flat_map(|k| [1, 2 * k, 1]))
+}
+
+/// An iterator over the convergents of a continued fraction, given its terms.
+///
+/// # Examples
+/// ```
+/// # use num::rational::Ratio;
+/// # use num::BigUint;
+/// # use pj_euler::utils::cfrac::{Convergents, SqrtExpansion};
+/// let ratio = |h: u32, k: u32| Ratio::new(BigUint::from(h), BigUint::from(k));
+/// let sqrt_2 = Convergents::new(SqrtExpansion::of(2).terms());
+/// assert_eq!(
+///     sqrt_2.take(5).collect::<Vec<_>>(),
+///     [ratio(1, 1), ratio(3, 2), ratio(7, 5), ratio(17, 12), ratio(41, 29)]
+/// );
+///
+/// // a finite continued fraction has finitely many convergents
+/// assert_eq!(Convergents::new([0, 1, 5, 2, 2]).last(), Some(ratio(27, 32)));
+/// ```
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::bignum::digit_sum;
+/// # use pj_euler::utils::cfrac::{e_terms, Convergents};
+/// let tenth = Convergents::new(e_terms()).nth(9).unwrap();
+/// assert_eq!(*tenth.numer(), BigUint::from(1457_u32));
+/// assert_eq!(*tenth.denom(), BigUint::from(536_u32));
+///
+/// let hundredth = Convergents::new(e_terms()).nth(99).unwrap();
+/// assert_eq!(digit_sum(hundredth.numer()), 272);
+/// ```
+pub struct Convergents<I> {
+    terms: I,
+
+    // (h(n-2), k(n-2)) and (h(n-1), k(n-1)) for the next convergent n.
+    before: (BigUint, BigUint),
+    last: (BigUint, BigUint),
+}
+
+impl<I: Iterator<Item = u64>> Convergents<I> {
+    /// Make an iterator over the convergents of the continued fraction with the given terms.
+    pub fn new(terms: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            terms: terms.into_iter(),
+            before: (BigUint::zero(), BigUint::one()),
+            last: (BigUint::one(), BigUint::zero()),
+        }
+    }
+}
+
+impl<I: Iterator<Item = u64>> Iterator for Convergents<I> {
+    type Item = Ratio<BigUint>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = BigUint::from(self.terms.next()?);
+        let next = (
+            &a * &self.last.0 + &self.before.0,
+            &a * &self.last.1 + &self.before.1,
+        );
+        self.before = std::mem::replace(&mut self.last, next);
+        Some(Ratio::new_raw(self.last.0.clone(), self.last.1.clone()))
+    }
+}
+
+/// An iterator over the positive solutions `(x, y)` of a Pell equation `x^2 - dy^2 = ±1`, in
+/// ascending order.
+///
+/// Every solution of `x^2 - dy^2 = 1` is `x + y sqrt(d) = (x1 + y1 sqrt(d))^k` for the fundamental
+/// solution `(x1, y1)`, so each solution comes from multiplying the previous one by it. The same
+/// goes for `x^2 - dy^2 = -1`, whose solutions are the odd powers of its own fundamental solution.
+pub struct PellSolutions {
+    d: BigUint,
+
+    // The fundamental solution of x^2 - dy^2 = 1, to multiply by.
+    step: (BigUint, BigUint),
+
+    // The next solution to yield.
+    next: (BigUint, BigUint),
+}
+
+impl Iterator for PellSolutions {
+    type Item = (BigUint, BigUint);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ((x1, y1), (x, y)) = (&self.step, &self.next);
+        let after = (x1 * x + &self.d * y1 * y, x1 * y + y1 * x);
+        Some(std::mem::replace(&mut self.next, after))
+    }
+}
+
+/// The convergent `h(i)/k(i)` of the square root of d, as a pair.
+fn sqrt_convergent(expansion: &SqrtExpansion, i: usize) -> (BigUint, BigUint) {
+    Convergents::new(expansion.terms())
+        .nth(i)
+        .expect("square roots of non-squares have infinitely many convergents")
+        .into_raw()
+}
+
+/// The solutions of Pell's equation `x^2 - dy^2 = 1` in positive integers, starting with the
+/// fundamental solution, or `None` if d is a perfect square.
+///
+/// With r the length of the period of `sqrt(d)`, the convergent `h(r-1)/k(r-1)` satisfies
+/// `h^2 - dk^2 = (-1)^r`, so the fundamental solution is that convergent when r is even, and
+/// `h(2r-1)/k(2r-1)` when r is odd.
+///
+/// # Examples
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::cfrac::pell;
+/// let solutions = |d| -> Vec<(u64, u64)> {
+///     pell(d)
+///         .unwrap()
+///         .take(3)
+///         .map(|(x, y)| (x.try_into().unwrap(), y.try_into().unwrap()))
+///         .collect()
+/// };
+/// assert_eq!(solutions(2), [(3, 2), (17, 12), (99, 70)]);
+/// assert_eq!(solutions(13), [(649, 180), (842_401, 233_640), (1_093_435_849, 303_264_540)]);
+/// assert!(pell(9).is_none());
+///
+/// // the fundamental solution for 61 is famously large
+/// let (x, y) = pell(61).unwrap().next().unwrap();
+/// assert_eq!(x, BigUint::from(1_766_319_049_u64));
+/// assert_eq!(y, BigUint::from(226_153_980_u64));
+/// ```
+#[must_use]
+pub fn pell(d: u64) -> Option<PellSolutions> {
+    let expansion = SqrtExpansion::of(d);
+    let r = expansion.period.len();
+    if r == 0 {
+        return None;
+    }
+    let fundamental = sqrt_convergent(
+        &expansion,
+        if r.is_multiple_of(2) {
+            r - 1
+        } else {
+            2 * r - 1
+        },
+    );
+    Some(PellSolutions {
+        d: BigUint::from(d),
+        step: fundamental.clone(),
+        next: fundamental,
+    })
+}
+
+/// The solutions of the negative Pell equation `x^2 - dy^2 = -1` in positive integers, starting
+/// with the fundamental solution, or `None` if there are none.
+///
+/// There are solutions exactly when the period of `sqrt(d)` has odd length r, and then the
+/// fundamental solution is the convergent `h(r-1)/k(r-1)`.
+///
+/// # Examples
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::cfrac::negative_pell;
+/// let mut solutions = negative_pell(13).unwrap();
+/// assert_eq!(solutions.next(), Some((BigUint::from(18_u32), BigUint::from(5_u32))));
+/// assert_eq!(solutions.next(), Some((BigUint::from(23_382_u32), BigUint::from(6485_u32))));
+///
+/// assert!(negative_pell(3).is_none());
+/// assert!(negative_pell(4).is_none());
+/// assert!(negative_pell(1).is_none());
+/// ```
+#[must_use]
+pub fn negative_pell(d: u64) -> Option<PellSolutions> {
+    let expansion = SqrtExpansion::of(d);
+    let r = expansion.period.len();
+    if r.is_multiple_of(2) {
+        return None;
+    }
+    let fundamental = sqrt_convergent(&expansion, r - 1);
+    Some(PellSolutions {
+        d: BigUint::from(d),
+        step: sqrt_convergent(&expansion, 2 * r - 1),
+        next: fundamental,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::{negative_pell, pell, SqrtExpansion};
+
+    #[test]
+    fn odd_periods_up_to_ten_thousand() {
+        let odd = (2..=10_000)
+            .filter(|&n| SqrtExpansion::of(n).period.len() % 2 == 1)
+            .count();
+        assert_eq!(odd, 1322);
+    }
+
+    #[test]
+    fn pell_solutions_satisfy_the_equation() {
+        for d in 2..200_u32 {
+            let d_big = BigUint::from(d);
+            if let Some(solutions) = pell(d.into()) {
+                for (x, y) in solutions.take(4) {
+                    assert_eq!(&x * &x, &d_big * &y * &y + 1_u32);
+                }
+            }
+            if let Some(solutions) = negative_pell(d.into()) {
+                for (x, y) in solutions.take(4) {
+                    assert_eq!(&x * &x + 1_u32, &d_big * &y * &y);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::pell;
+
+    extern crate test;
+    use test::Bencher;
+
+    #[bench]
+    fn largest_fundamental_pell_solution(b: &mut Bencher) {
+        b.iter(|| {
+            let d = (2..=1000)
+                .filter_map(|d| Some((pell(d)?.next()?.0, d)))
+                .max()
+                .map(|(_, d)| d);
+            assert_eq!(d, Some(661));
+        });
+    }
+}
@@ -0,0 +1,214 @@
+//! Utilities for the digits of a number in an arbitrary base.
+
+use num::{Num, NumCast};
+
+use super::num_fn::{_0, _1};
+
+/// An iterator over the digits of a number, least significant first.
+///
+/// It's double-ended, so `.rev()` gives the digits most significant first. Zero has a single
+/// digit.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::digits::Digits;
+/// assert_eq!(Digits::of(1234).collect::<Vec<_>>(), [4, 3, 2, 1]);
+/// assert_eq!(Digits::of(1234).rev().collect::<Vec<_>>(), [1, 2, 3, 4]);
+/// assert_eq!(Digits::in_base(6, 2).rev().collect::<Vec<_>>(), [1, 1, 0]);
+/// assert_eq!(Digits::of(0).collect::<Vec<_>>(), [0]);
+/// assert_eq!(Digits::of(1_000_000).len(), 7);
+/// ```
+/// ```
+/// # use pj_euler::utils::digits::Digits;
+/// // take from both ends at once
+/// let mut d = Digits::of(12_345_u32);
+/// assert_eq!(d.next(), Some(5));
+/// assert_eq!(d.next_back(), Some(1));
+/// assert_eq!(d.next_back(), Some(2));
+/// assert_eq!(d.next(), Some(4));
+/// assert_eq!(d.next(), Some(3));
+/// assert_eq!(d.next_back(), None);
+/// ```
+pub struct Digits<T> {
+    // The number made of the remaining digits.
+    n: T,
+
+    base: T,
+
+    // base^(len - 1), the place value of the most significant remaining digit.
+    power: T,
+
+    // The number of remaining digits.
+    len: usize,
+}
+
+impl<N: Num + PartialOrd + Copy> Digits<N> {
+    /// The decimal digits of n.
+    #[must_use]
+    pub fn of(n: N) -> Self {
+        let ten = (0..10).fold(_0(), |ten: N, _| ten + _1());
+        Self::in_base(n, ten)
+    }
+
+    /// The digits of n in the given base.
+    ///
+    /// # Panics
+    /// Panics if the base is less than 2.
+    #[must_use]
+    pub fn in_base(n: N, base: N) -> Self {
+        assert!(base > _1(), "base is at least 2");
+        let (mut power, mut len) = (_1(), 1);
+        while n / power >= base {
+            power = power * base;
+            len += 1;
+        }
+        Self {
+            n,
+            base,
+            power,
+            len,
+        }
+    }
+}
+
+impl<N: Num + PartialOrd + Copy> Iterator for Digits<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.n % self.base;
+        self.n = self.n / self.base;
+        self.power = self.power / self.base;
+        self.len -= 1;
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<N: Num + PartialOrd + Copy> DoubleEndedIterator for Digits<N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.n / self.power;
+        self.n = self.n % self.power;
+        self.power = self.power / self.base;
+        self.len -= 1;
+        Some(digit)
+    }
+}
+
+impl<N: Num + PartialOrd + Copy> ExactSizeIterator for Digits<N> {}
+
+/// Build a number from its digits in the given base, most significant first.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::digits::{from_digits, Digits};
+/// assert_eq!(from_digits([1, 2, 3, 4], 10), 1234);
+/// assert_eq!(from_digits([1, 1, 0], 2), 6);
+/// assert_eq!(from_digits(Digits::in_base(987_654, 7).rev(), 7), 987_654);
+/// ```
+#[must_use]
+pub fn from_digits<N: Num + Copy>(digits: impl IntoIterator<Item = N>, base: N) -> N {
+    digits.into_iter().fold(_0(), |n, d| n * base + d)
+}
+
+/// Reverse the digits of n in the given base; trailing zeros become leading zeros and are lost.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::digits::reverse_number;
+/// assert_eq!(reverse_number(1234, 10), 4321);
+/// assert_eq!(reverse_number(1200, 10), 21);
+/// assert_eq!(reverse_number(6, 2), 3);
+/// ```
+#[must_use]
+pub fn reverse_number<N: Num + PartialOrd + Copy>(n: N, base: N) -> N {
+    from_digits(Digits::in_base(n, base), base)
+}
+
+/// The sum of the digits of n in the given base.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::digits::digit_sum;
+/// assert_eq!(digit_sum(9875, 10), 29);
+/// assert_eq!(digit_sum(255, 2), 8);
+/// ```
+#[must_use]
+pub fn digit_sum<N: Num + PartialOrd + Copy>(n: N, base: N) -> N {
+    Digits::in_base(n, base).fold(_0(), |sum, d| sum + d)
+}
+
+/// Check whether n reads the same forwards and backwards in the given base.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::digits::is_palindrome_in_base;
+/// assert!(is_palindrome_in_base(585, 10));
+/// assert!(is_palindrome_in_base(585, 2)); // 1001001001
+/// assert!(is_palindrome_in_base(100_343_001, 10));
+/// assert!(!is_palindrome_in_base(12, 10));
+/// assert!(!is_palindrome_in_base(10, 10));
+/// ```
+#[must_use]
+pub fn is_palindrome_in_base<N: Num + PartialOrd + Copy>(n: N, base: N) -> bool {
+    let mut digits = Digits::in_base(n, base);
+    while let (Some(low), Some(high)) = (digits.next(), digits.next_back()) {
+        if low != high {
+            return false;
+        }
+    }
+    true
+}
+
+/// Check whether the k digits of n in the given base are exactly `1, 2, ..., k` in some order.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::digits::is_pandigital;
+/// assert!(is_pandigital(192_384_576, 10));
+/// assert!(is_pandigital(2143, 10));
+/// assert!(!is_pandigital(1_023_456_789_u64, 10));
+/// assert!(!is_pandigital(1224, 10));
+/// assert!(is_pandigital(1, 2));
+/// assert!(!is_pandigital(0b10, 2));
+/// ```
+#[must_use]
+pub fn is_pandigital<N: Num + NumCast + PartialOrd + Copy>(n: N, base: N) -> bool {
+    let digits = Digits::in_base(n, base);
+    let mut seen = vec![false; digits.len()];
+    for d in digits {
+        let d: usize = num::cast(d).expect("digit fits in usize");
+        if d == 0 || d > seen.len() || seen[d - 1] {
+            return false;
+        }
+        seen[d - 1] = true;
+    }
+    true
+}
+
+/// An iterator over the cyclic rotations of the digits of n in the given base, starting with n
+/// and moving the leading digit to the end each time.
+///
+/// Leading zeros produced by a rotation are kept for later rotations, but not in the yielded
+/// numbers.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::digits::rotations;
+/// assert_eq!(rotations(197, 10).collect::<Vec<_>>(), [197, 971, 719]);
+/// assert_eq!(rotations(101, 10).collect::<Vec<_>>(), [101, 11, 110]);
+/// assert_eq!(rotations(7, 10).collect::<Vec<_>>(), [7]);
+/// ```
+pub fn rotations<N: Num + PartialOrd + Copy>(n: N, base: N) -> impl Iterator<Item = N> {
+    let digits = Digits::in_base(n, base);
+    let (len, power) = (digits.len(), digits.power);
+    std::iter::successors(Some(n), move |&r| Some(r % power * base + r / power)).take(len)
+}
@@ -0,0 +1,287 @@
+//! Summatory functions of arithmetic functions, and the Dirichlet hyperbola method.
+//!
+//! An arithmetic function f is represented by its summatory function `F(v) = f(1) + ... + f(v)`,
+//! evaluated at every `v <= limit` and at every `v = n / i` above the limit. That's enough to
+//! compute the same values of `f * g`, the Dirichlet convolution of two functions, since
+//!
+//! ```text
+//! (f * g)(k) = sum_{ab = k} f(a) g(b)
+//! ```
+//!
+//! and the summatory function of `f * g` at v splits along the hyperbola `ab = v` into sums over
+//! `a, b <= sqrt(v)`, each of which only needs values of F and G at points of the form `v / a`.
+
+use num::Num;
+
+use crate::utils::num_fn::{_0, _1};
+use crate::utils::primes::{mobius_up_to, totients_up_to};
+
+/// The summatory function of an arithmetic function, at the points needed for the hyperbola
+/// method.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::dirichlet::PrefixSums;
+/// # use pj_euler::utils::primes::number_of_divisors;
+/// // the constant function 1 convolved with itself counts divisors
+/// let one = PrefixSums::from_summatory(1000, 100, |v| v);
+/// let d = one.convolve(&one);
+/// assert_eq!(d.get(1000), (1..=1000).map(number_of_divisors).sum());
+/// assert_eq!(d.get(1000 / 7), (1..=1000 / 7).map(number_of_divisors).sum());
+/// assert_eq!(d.value(28), 6);
+/// ```
+/// ```should_panic
+/// # use pj_euler::utils::dirichlet::PrefixSums;
+/// // 150 is above the limit, but isn't 1000 / i for any i
+/// let one = PrefixSums::from_summatory(1000, 100, |v| v);
+/// let _ = one.get(150);
+/// ```
+pub struct PrefixSums<T> {
+    n: u64,
+
+    // F(v) for v <= limit, where the limit is small.len() - 1.
+    small: Vec<T>,
+
+    // F(n / i) for 1 <= i < large.len(), which are exactly the n / i above the limit.
+    large: Vec<T>,
+}
+
+/// A good choice of limit for sublinear summatory functions of `n`, namely `n^(2/3)`.
+///
+/// Small values can be sieved in about linear time, and each large value costs `O(sqrt(n / i))`,
+/// so this balances the two.
+#[must_use]
+pub fn default_limit(n: u64) -> u64 {
+    ((n as f64).cbrt() as u64).pow(2).max(n.isqrt())
+}
+
+impl<T: Num + Copy> PrefixSums<T> {
+    /// Tabulate a summatory function given in closed form.
+    ///
+    /// # Panics
+    /// Panics if the limit is less than the square root of n.
+    pub fn from_summatory(n: u64, limit: u64, summatory: impl Fn(u64) -> T) -> Self {
+        assert!(limit >= n.isqrt(), "limit is at least sqrt(n)");
+        Self {
+            n,
+            small: (0..=limit).map(&summatory).collect(),
+            large: (0..=n / (limit + 1))
+                .map(|i| n.checked_div(i).map_or_else(_0, &summatory))
+                .collect(),
+        }
+    }
+
+    /// The point up to which every value of the summatory function is known.
+    #[must_use]
+    pub fn limit(&self) -> u64 {
+        self.small.len() as u64 - 1
+    }
+
+    /// The summatory function at `v`.
+    ///
+    /// # Panics
+    /// Panics unless v is at most the limit or of the form `n / i`.
+    #[must_use]
+    pub fn get(&self, v: u64) -> T {
+        if v <= self.limit() {
+            self.small[v as usize]
+        } else {
+            assert!(
+                v <= self.n && self.n / (self.n / v) == v,
+                "v is at most the limit or of the form n / i"
+            );
+            self.large[(self.n / v) as usize]
+        }
+    }
+
+    /// The function itself at `k`, which must be at most the limit.
+    ///
+    /// # Panics
+    /// Panics if k is 0 or above the limit.
+    #[must_use]
+    pub fn value(&self, k: u64) -> T {
+        self.small[k as usize] - self.small[k as usize - 1]
+    }
+
+    /// The values of the function up to the limit; the entry for 0 is 0.
+    fn values(&self) -> Vec<T> {
+        std::iter::once(_0())
+            .chain(self.small.windows(2).map(|w| w[1] - w[0]))
+            .collect()
+    }
+
+    /// Make the summatory function of the given values up to the limit, and fill in the rest with
+    /// `large(v)`, which may use the values of F at `n / j` for `j > i` when computing F at
+    /// `v = n / i`.
+    fn from_values(n: u64, values: &[T], large: impl Fn(&Self, u64) -> T) -> Self {
+        let limit = values.len() as u64 - 1;
+        let mut out = Self {
+            n,
+            small: values
+                .iter()
+                .scan(_0(), |sum, &v| {
+                    *sum = *sum + v;
+                    Some(*sum)
+                })
+                .collect(),
+            large: vec![_0(); (n / (limit + 1) + 1) as usize],
+        };
+        for i in (1..out.large.len()).rev() {
+            out.large[i] = large(&out, n / i as u64);
+        }
+        out
+    }
+
+    /// The Dirichlet convolution of two functions.
+    ///
+    /// # Panics
+    /// Panics if the two functions are tabulated at different points.
+    #[must_use]
+    pub fn convolve(&self, other: &Self) -> Self {
+        assert!(self.n == other.n && self.limit() == other.limit());
+        let (f, g) = (self.values(), other.values());
+
+        // sieve the small values directly
+        let mut h = vec![_0(); f.len()];
+        for a in 1..f.len() {
+            for b in 1..=(f.len() - 1) / a {
+                h[a * b] = h[a * b] + f[a] * g[b];
+            }
+        }
+
+        Self::from_values(self.n, &h, |_, v| {
+            let s = v.isqrt();
+            let mut sum = _0::<T>();
+            for a in 1..=s {
+                sum = sum + f[a as usize] * other.get(v / a) + g[a as usize] * self.get(v / a);
+            }
+            sum - self.get(s) * other.get(s)
+        })
+    }
+
+    /// The Dirichlet quotient `f / g`, i.e. the function `q` with `q * g = f`.
+    ///
+    /// # Panics
+    /// Panics if `g(1)` is not 1, or if the two functions are tabulated at different points.
+    ///
+    /// # Examples
+    /// ```
+    /// # use pj_euler::utils::dirichlet::PrefixSums;
+    /// # use pj_euler::utils::primes::mobius;
+    /// // the Dirichlet inverse of 1 is the Möbius function
+    /// let epsilon = PrefixSums::from_summatory(1000, 100, |v| i64::from(v > 0));
+    /// let one = PrefixSums::from_summatory(1000, 100, |v| v as i64);
+    /// let mu = epsilon.divide(&one);
+    /// assert_eq!(mu.get(1000), (1..=1000).map(|n| i64::from(mobius(n))).sum());
+    /// assert!((1..=100).all(|n| mu.value(n) == i64::from(mobius(n))));
+    /// ```
+    #[must_use]
+    pub fn divide(&self, other: &Self) -> Self {
+        assert!(self.n == other.n && self.limit() == other.limit());
+        assert!(other.value(1) == _1(), "g(1) is 1");
+        let g = other.values();
+
+        // sieve the small values, peeling off each q(a) g(b) from h(ab)
+        let mut q = self.values();
+        for a in 1..q.len() {
+            for b in 2..=(q.len() - 1) / a {
+                q[a * b] = q[a * b] - q[a] * g[b];
+            }
+        }
+
+        self.quotient_with(other, &q)
+    }
+
+    /// The Dirichlet quotient `f / g`, given its values up to the limit.
+    fn quotient_with(&self, other: &Self, q: &[T]) -> Self {
+        let g = other.values();
+        Self::from_values(self.n, q, |quotient, v| {
+            // F(v) = sum_{ab <= v} q(a) g(b), where the only unknown term is Q(v) g(1)
+            let s = v.isqrt();
+            let mut known = _0::<T>();
+            for a in 1..=s {
+                known = known + q[a as usize] * other.get(v / a);
+                if a >= 2 {
+                    known = known + g[a as usize] * quotient.get(v / a);
+                }
+            }
+            self.get(v) + quotient.get(s) * other.get(s) - known
+        })
+    }
+}
+
+/// The Mertens function M(n), the sum of μ(k) for k up to n, in `O(n^(2/3))` time.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::dirichlet::mertens;
+/// # use pj_euler::utils::primes::mobius;
+/// assert_eq!(mertens(1), 1);
+/// assert_eq!(mertens(1000), 2);
+/// assert!((1..300).all(|n| mertens(n) == (1..=n).map(|k| i64::from(mobius(k))).sum()));
+/// ```
+#[must_use]
+pub fn mertens(n: u64) -> i64 {
+    let limit = default_limit(n);
+    let epsilon = PrefixSums::from_summatory(n, limit, |v| i64::from(v > 0));
+    let one = PrefixSums::from_summatory(n, limit, |v| v as i64);
+    let mu: Vec<_> = mobius_up_to(limit as usize)
+        .into_iter()
+        .map(i64::from)
+        .collect();
+    epsilon.quotient_with(&one, &mu).get(n)
+}
+
+/// The totient summatory function Φ(n), the sum of φ(k) for k up to n, in `O(n^(2/3))` time.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::dirichlet::totient_sum;
+/// # use pj_euler::utils::primes::totient;
+/// assert_eq!(totient_sum(1), 1);
+/// assert_eq!(totient_sum(10), 32);
+/// assert!((1..300).all(|n| totient_sum(n) == (1..=n).map(|k| u128::from(totient(k))).sum()));
+/// ```
+#[must_use]
+pub fn totient_sum(n: u64) -> u128 {
+    let limit = default_limit(n);
+    let id = PrefixSums::from_summatory(n, limit, |v| u128::from(v) * (u128::from(v) + 1) / 2);
+    let one = PrefixSums::from_summatory(n, limit, u128::from);
+    let phi: Vec<_> = totients_up_to(limit as usize)
+        .into_iter()
+        .map(|phi| phi as u128)
+        .collect();
+    id.quotient_with(&one, &phi).get(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mertens, totient_sum};
+
+    #[test]
+    fn mertens_powers_of_ten() {
+        let known = [-1, 1, 2, -23, -48, 212, 1037, 1928, -222, -33722];
+        for (k, m) in (1..).zip(known) {
+            assert_eq!(mertens(10_u64.pow(k)), m);
+        }
+    }
+
+    #[test]
+    fn totient_sum_powers_of_ten() {
+        let known = [
+            32,
+            3044,
+            304_192,
+            30_397_486,
+            3_039_650_754,
+            303_963_552_392,
+            30_396_356_427_242,
+            3_039_635_516_365_908,
+            303_963_551_173_008_414,
+            30_396_355_092_886_216_366,
+        ];
+        for (k, phi) in (1..).zip(known) {
+            assert_eq!(totient_sum(10_u64.pow(k)), phi);
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! Integers modulo a fixed modulus.
+//!
+//! [`ModInt`] fixes its modulus at compile time, and [`DynModInt`] reads it from a thread-local
+//! set by [`DynModInt::set_modulus`]. Both implement `num::Num` and `num::NumCast`, so they can be
+//! used with the generic utilities elsewhere in the crate. Division multiplies by the modular
+//! inverse, so it's only defined for divisors coprime to the modulus; when the divisor divides the
+//! dividend as integers, this agrees with integer division.
+//!
+//! For raw speed on 64-bit moduli, [`Montgomery`] provides multiplication without division.
+
+mod montgomery;
+
+use std::cell::Cell;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+use num::{Num, One, ToPrimitive, Zero};
+
+pub use montgomery::Montgomery;
+
+use super::number_theory::{mod_inverse, mul_mod, pow_mod};
+
+/// An integer modulo `M`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::modular::ModInt;
+/// type Mod7 = ModInt<7>;
+/// assert_eq!(Mod7::new(5) + Mod7::new(4), Mod7::new(2));
+/// assert_eq!(Mod7::new(2) - Mod7::new(4), Mod7::new(5));
+/// assert_eq!(Mod7::new(3) * Mod7::new(5), Mod7::new(1));
+/// assert_eq!(Mod7::new(1) / Mod7::new(3), Mod7::new(5));
+/// assert_eq!(Mod7::new(3).pow(6), Mod7::new(1));
+/// assert_eq!(Mod7::new(100).value(), 2);
+/// ```
+/// ```
+/// # use pj_euler::utils::modular::ModInt;
+/// # use pj_euler::utils::primes::number_of_divisors;
+/// # use pj_euler::utils::seqs::Triangle;
+/// type Mod = ModInt<1_000_000_007>;
+///
+/// // T(n) = n(n + 1) / 2, with the division done by the inverse of 2
+/// let t = Triangle::<Mod>::new().nth(1_000_000_000_000).unwrap();
+/// let expected = 1_000_000_000_000_u128 * 1_000_000_000_001 / 2 % 1_000_000_007;
+/// assert_eq!(t, Mod::new(expected as u64));
+///
+/// assert_eq!(number_of_divisors(Mod::new(28)), Mod::new(6));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    /// Reduce `value` modulo `M`.
+    #[must_use]
+    pub const fn new(value: u64) -> Self {
+        Self { value: value % M }
+    }
+
+    /// The modulus.
+    #[must_use]
+    pub const fn modulus() -> u64 {
+        M
+    }
+}
+
+thread_local! {
+    // 0 until the modulus is set, so that using it before then is caught.
+    static MODULUS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// An integer modulo a modulus chosen at runtime.
+///
+/// The modulus is shared by every `DynModInt` on the current thread, and must be set before any
+/// values are made; changing it invalidates any values made under the old modulus.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::modular::DynModInt;
+/// DynModInt::set_modulus(13);
+/// assert_eq!(DynModInt::new(10) + DynModInt::new(5), DynModInt::new(2));
+/// assert_eq!(DynModInt::new(2).pow(12), DynModInt::new(1));
+/// assert_eq!(DynModInt::new(1) / DynModInt::new(2), DynModInt::new(7));
+/// ```
+/// ```should_panic
+/// # use pj_euler::utils::modular::DynModInt;
+/// // the modulus hasn't been set on this thread
+/// let _ = DynModInt::new(5);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DynModInt {
+    value: u64,
+}
+
+impl DynModInt {
+    /// Set the modulus for the current thread.
+    ///
+    /// # Panics
+    /// Panics if the modulus is 0.
+    pub fn set_modulus(modulus: u64) {
+        assert!(modulus > 0, "modulus is positive");
+        MODULUS.with(|m| m.set(modulus));
+    }
+
+    /// The modulus for the current thread.
+    ///
+    /// # Panics
+    /// Panics if the modulus hasn't been set on this thread.
+    #[must_use]
+    pub fn modulus() -> u64 {
+        let modulus = MODULUS.with(Cell::get);
+        assert!(modulus > 0, "the modulus is set before use");
+        modulus
+    }
+
+    /// Reduce `value` modulo the current modulus.
+    ///
+    /// # Panics
+    /// Panics if the modulus hasn't been set on this thread.
+    #[must_use]
+    pub fn new(value: u64) -> Self {
+        Self {
+            value: value % Self::modulus(),
+        }
+    }
+}
+
+/// Implement the arithmetic and `num` traits for a modular integer type.
+macro_rules! modular {
+    ([$($generics:tt)*] $ty:ty, $modulus:expr) => {
+        impl<$($generics)*> $ty {
+            /// The canonical representative, in `0..modulus`.
+            #[must_use]
+            pub const fn value(self) -> u64 {
+                self.value
+            }
+
+            /// Raise to the power `exp` by square-and-multiply.
+            #[must_use]
+            pub fn pow(self, exp: u64) -> Self {
+                Self {
+                    value: pow_mod(self.value, exp, $modulus),
+                }
+            }
+
+            /// The multiplicative inverse, if it exists.
+            #[must_use]
+            pub fn inverse(self) -> Option<Self> {
+                mod_inverse(i128::from(self.value), i128::from($modulus)).map(|value| Self {
+                    value: value as u64,
+                })
+            }
+        }
+
+        impl<$($generics)*> From<u64> for $ty {
+            fn from(value: u64) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl<$($generics)*> fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.value.fmt(f)
+            }
+        }
+
+        impl<$($generics)*> Add for $ty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                let m = $modulus;
+                let value = if self.value >= m - rhs.value {
+                    self.value - (m - rhs.value)
+                } else {
+                    self.value + rhs.value
+                };
+                Self { value }
+            }
+        }
+
+        impl<$($generics)*> Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                self + -rhs
+            }
+        }
+
+        impl<$($generics)*> Neg for $ty {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                if self.value == 0 {
+                    self
+                } else {
+                    Self {
+                        value: $modulus - self.value,
+                    }
+                }
+            }
+        }
+
+        impl<$($generics)*> Mul for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self {
+                Self {
+                    value: mul_mod(self.value, rhs.value, $modulus),
+                }
+            }
+        }
+
+        impl<$($generics)*> Div for $ty {
+            type Output = Self;
+
+            /// Multiply by the inverse of `rhs`.
+            ///
+            /// # Panics
+            /// Panics if `rhs` is not coprime to the modulus.
+            #[allow(clippy::suspicious_arithmetic_impl)] // division is multiplication by the inverse
+            fn div(self, rhs: Self) -> Self {
+                self * rhs.inverse().expect("divisor is invertible")
+            }
+        }
+
+        impl<$($generics)*> Rem for $ty {
+            type Output = Self;
+
+            /// The remainder of the canonical representatives.
+            fn rem(self, rhs: Self) -> Self {
+                Self {
+                    value: self.value % rhs.value,
+                }
+            }
+        }
+
+        impl<$($generics)*> AddAssign for $ty {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<$($generics)*> SubAssign for $ty {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<$($generics)*> MulAssign for $ty {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<$($generics)*> DivAssign for $ty {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl<$($generics)*> RemAssign for $ty {
+            fn rem_assign(&mut self, rhs: Self) {
+                *self = *self % rhs;
+            }
+        }
+
+        impl<$($generics)*> Sum for $ty {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::zero(), Add::add)
+            }
+        }
+
+        impl<$($generics)*> Product for $ty {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::one(), Mul::mul)
+            }
+        }
+
+        impl<$($generics)*> Zero for $ty {
+            fn zero() -> Self {
+                Self { value: 0 }
+            }
+
+            fn is_zero(&self) -> bool {
+                self.value == 0
+            }
+        }
+
+        impl<$($generics)*> One for $ty {
+            fn one() -> Self {
+                Self::new(1)
+            }
+        }
+
+        impl<$($generics)*> Num for $ty {
+            type FromStrRadixErr = std::num::ParseIntError;
+
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+                u64::from_str_radix(s, radix).map(Self::new)
+            }
+        }
+
+        impl<$($generics)*> ToPrimitive for $ty {
+            fn to_i64(&self) -> Option<i64> {
+                self.value.to_i64()
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                Some(self.value)
+            }
+        }
+
+        impl<$($generics)*> num::NumCast for $ty {
+            /// Reduce `n` modulo the modulus, taking the least nonnegative residue of negatives.
+            fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+                let m: i128 = $modulus.into();
+                n.to_i128().map(|n| Self {
+                    value: n.rem_euclid(m) as u64,
+                })
+            }
+        }
+    };
+}
+
+modular!([const M: u64] ModInt<M>, M);
+modular!([] DynModInt, DynModInt::modulus());
+
+#[cfg(test)]
+mod benches {
+    use super::Montgomery;
+
+    extern crate test;
+    use test::{black_box, Bencher};
+
+    const MODULUS: u64 = 18_446_744_073_709_551_557;
+
+    #[bench]
+    fn naive_pow(b: &mut Bencher) {
+        b.iter(|| {
+            let (mut out, mut base, mut exp) = (1_u64, black_box(3_u64), MODULUS - 1);
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    out = (u128::from(out) * u128::from(base) % u128::from(MODULUS)) as u64;
+                }
+                base = (u128::from(base) * u128::from(base) % u128::from(MODULUS)) as u64;
+                exp >>= 1;
+            }
+            assert_eq!(out, 1);
+        });
+    }
+
+    #[bench]
+    fn montgomery_pow(b: &mut Bencher) {
+        let m = Montgomery::new(MODULUS);
+        b.iter(|| {
+            let base = m.to_montgomery(black_box(3));
+            assert_eq!(m.from_montgomery(m.pow(base, MODULUS - 1)), 1);
+        });
+    }
+}
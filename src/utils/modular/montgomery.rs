@@ -0,0 +1,118 @@
+//! Montgomery multiplication modulo a 64-bit odd modulus.
+
+/// A context for arithmetic modulo an odd `n` in Montgomery form, with `R = 2^64`.
+///
+/// A residue `a` is represented by `aR mod n`. In that form, multiplication needs no division
+/// by n, just two 64-bit multiplications and a shift, which is much faster than reducing a
+/// `u128` product with `%`. Every value passed to or returned from the arithmetic methods is in
+/// Montgomery form; use [`Montgomery::to_montgomery`] and [`Montgomery::from_montgomery`] to
+/// convert.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::modular::Montgomery;
+/// let m = Montgomery::new(1_000_000_007);
+/// let (a, b) = (m.to_montgomery(123_456_789), m.to_montgomery(987_654_321));
+/// assert_eq!(m.from_montgomery(m.mul(a, b)), 123_456_789 * 987_654_321 % 1_000_000_007);
+/// assert_eq!(m.from_montgomery(m.pow(a, 1_000_000_006)), 1);
+/// ```
+/// ```
+/// # use pj_euler::utils::modular::Montgomery;
+/// let n = 18_446_744_073_709_551_557; // the largest prime below 2^64
+/// let m = Montgomery::new(n);
+/// let a = n - 2;
+/// let expected = (u128::from(a) * u128::from(a) % u128::from(n)) as u64;
+/// assert_eq!(m.from_montgomery(m.mul(m.to_montgomery(a), m.to_montgomery(a))), expected);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Montgomery {
+    n: u64,
+
+    // n^-1 mod R.
+    inv: u64,
+
+    // R^2 mod n, used to convert into Montgomery form.
+    r2: u64,
+}
+
+impl Montgomery {
+    /// Make a context for arithmetic modulo n.
+    ///
+    /// # Panics
+    /// Panics if n is even.
+    #[must_use]
+    pub fn new(n: u64) -> Self {
+        assert!(n % 2 == 1, "modulus is odd");
+
+        // Newton's method doubles the number of correct low bits each step, and n is its own
+        // inverse mod 8, so five steps give all 64 bits
+        let mut inv = n;
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2_u64.wrapping_sub(n.wrapping_mul(inv)));
+        }
+
+        let r = ((1_u128 << 64) % u128::from(n)) as u64;
+        let r2 = (u128::from(r) * u128::from(r) % u128::from(n)) as u64;
+        Self { n, inv, r2 }
+    }
+
+    /// The modulus.
+    #[must_use]
+    pub const fn modulus(&self) -> u64 {
+        self.n
+    }
+
+    /// Compute `t / R mod n`, for `t < nR`.
+    const fn reduce(&self, t: u128) -> u64 {
+        // m is chosen so that mn = t mod R, so t - mn is exactly divisible by R
+        let m = (t as u64).wrapping_mul(self.inv);
+        let mn = m as u128 * self.n as u128;
+        let (t_hi, mn_hi) = ((t >> 64) as u64, (mn >> 64) as u64);
+        if t_hi >= mn_hi {
+            t_hi - mn_hi
+        } else {
+            t_hi.wrapping_sub(mn_hi).wrapping_add(self.n)
+        }
+    }
+
+    /// Convert `a` into Montgomery form.
+    #[must_use]
+    pub const fn to_montgomery(&self, a: u64) -> u64 {
+        self.reduce((a % self.n) as u128 * self.r2 as u128)
+    }
+
+    /// Convert `a` out of Montgomery form.
+    #[must_use]
+    pub const fn from_montgomery(&self, a: u64) -> u64 {
+        self.reduce(a as u128)
+    }
+
+    /// The Montgomery form of 1.
+    #[must_use]
+    pub const fn one(&self) -> u64 {
+        self.to_montgomery(1)
+    }
+
+    /// Multiply two numbers in Montgomery form.
+    #[must_use]
+    pub const fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// Raise a number in Montgomery form to the power `exp` by square-and-multiply.
+    ///
+    /// This is [`pow_mod`](crate::utils::number_theory::pow_mod) with every multiplication done
+    /// in Montgomery form, which is where the speed comes from.
+    #[must_use]
+    pub const fn pow(&self, mut base: u64, mut exp: u64) -> u64 {
+        let mut out = self.one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                out = self.mul(out, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        out
+    }
+}
@@ -0,0 +1,443 @@
+//! Elementary number theory: Bezout coefficients, inverses, congruences, multiplicative orders,
+//! and modular roots and logarithms.
+
+use std::collections::HashMap;
+
+use num::{Integer, Signed};
+
+use super::num_fn::{_0, _1};
+use super::primes::{fast_totient, PrimeFactor, PrimeFactorization};
+
+/// The extended Euclidean algorithm.
+///
+/// Returns `(g, x, y)` where `g = gcd(a, b)` is nonnegative and `ax + by = g`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::ext_gcd;
+/// assert_eq!(ext_gcd(240, 46), (2, -9, 47));
+/// assert_eq!(ext_gcd(0, 5), (5, 0, 1));
+/// ```
+/// ```
+/// # use pj_euler::utils::number_theory::ext_gcd;
+/// # use num::Integer;
+/// for a in -50_i64..50 {
+///     for b in -50..50 {
+///         let (g, x, y) = ext_gcd(a, b);
+///         assert_eq!(g, a.gcd(&b));
+///         assert_eq!(a * x + b * y, g);
+///     }
+/// }
+/// ```
+pub fn ext_gcd<N: Integer + Signed + Copy>(a: N, b: N) -> (N, N, N) {
+    // maintain old_x * a + old_y * b = old_r and x * a + y * b = r
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (_1(), _0());
+    let (mut old_y, mut y) = (_0(), _1());
+    while !r.is_zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of a modulo m, in `0..m`, if a is coprime to m.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::mod_inverse;
+/// assert_eq!(mod_inverse(3, 7), Some(5));
+/// assert_eq!(mod_inverse(-3, 7), Some(2));
+/// assert_eq!(mod_inverse(4, 8), None);
+/// assert_eq!(mod_inverse(1, 1), Some(0));
+/// ```
+pub fn mod_inverse<N: Integer + Signed + Copy>(a: N, m: N) -> Option<N> {
+    let (g, x, _) = ext_gcd(a, m);
+    g.is_one().then(|| x.mod_floor(&m))
+}
+
+/// Solve the simultaneous congruences `x = r mod m` for each `(r, m)`.
+///
+/// The moduli needn't be pairwise coprime. Returns `(x, l)`, where every solution is congruent to
+/// x modulo l, the lcm of the moduli, and `0 <= x < l`; or `None` if the congruences are
+/// inconsistent. Intermediate values are as large as the product of two moduli, so use a wide
+/// enough type.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::crt;
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+/// assert_eq!(crt(&[(3, 4), (4, 6)]), None);
+/// assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+/// ```
+/// ```
+/// # use pj_euler::utils::number_theory::crt;
+/// for (a, b) in (0..12).flat_map(|a| (0..18).map(move |b| (a, b))) {
+///     let brute = (0..36).find(|x| x % 12 == a && x % 18 == b);
+///     assert_eq!(crt(&[(a, 12), (b, 18)]), brute.map(|x| (x, 36)));
+/// }
+/// ```
+pub fn crt<N: Integer + Signed + Copy>(congruences: &[(N, N)]) -> Option<(N, N)> {
+    congruences
+        .iter()
+        .try_fold((_0(), _1()), |(r1, m1), &(r2, m2)| {
+            // x = r1 + m1 k, so we need m1 k = r2 - r1 mod m2, solvable iff g | r2 - r1
+            let (g, inv, _) = ext_gcd(m1, m2);
+            let (diff, rem) = (r2 - r1).div_rem(&g);
+            if !rem.is_zero() {
+                return None;
+            }
+
+            let m2_g = m2 / g;
+            let k = (diff * inv).mod_floor(&m2_g);
+            let l = m1 * m2_g;
+            Some(((r1 + m1 * k).mod_floor(&l), l))
+        })
+}
+
+/// Compute `a * b mod m` without overflowing.
+///
+/// This and [`pow_mod`] are the plain `u64` versions that [`ModInt`](super::modular::ModInt)
+/// builds on. Many multiplications by the same modulus are faster in
+/// [`Montgomery`](super::modular::Montgomery) form.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::mul_mod;
+/// assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 1, u64::MAX), 1);
+/// ```
+#[must_use]
+pub const fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// Compute `base^exp mod m` by square-and-multiply.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::pow_mod;
+/// assert_eq!(pow_mod(2, 10, 1000), 24);
+/// assert_eq!(pow_mod(3, 0, 1), 0);
+/// assert_eq!(pow_mod(3, 1_000_000_006, 1_000_000_007), 1);
+/// ```
+#[must_use]
+pub const fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut out = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            out = mul_mod(out, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    out
+}
+
+/// The Jacobi symbol (a/n), for odd positive n.
+///
+/// When n is prime this is the Legendre symbol; see [`legendre`].
+///
+/// # Panics
+/// Panics if n is even.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::jacobi;
+/// assert_eq!(jacobi(1001, 9907), -1);
+/// assert_eq!(jacobi(19, 45), 1);
+/// assert_eq!(jacobi(8, 21), -1);
+/// assert_eq!(jacobi(5, 21), 1);
+/// assert_eq!(jacobi(3, 21), 0);
+/// ```
+#[must_use]
+pub fn jacobi(a: u64, n: u64) -> i8 {
+    assert!(n % 2 == 1, "n is odd");
+    let (mut a, mut n) = (a % n, n);
+    let mut out = 1;
+    while a != 0 {
+        // (2/n) is -1 exactly when n is 3 or 5 mod 8
+        while a % 2 == 0 {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                out = -out;
+            }
+        }
+
+        // quadratic reciprocity
+        (a, n) = (n, a);
+        if a % 4 == 3 && n % 4 == 3 {
+            out = -out;
+        }
+        a %= n;
+    }
+
+    if n == 1 {
+        out
+    } else {
+        0
+    }
+}
+
+/// The Legendre symbol (a/p), for odd prime p: 0 if p divides a, 1 if a is a nonzero square mod
+/// p, and -1 otherwise.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::legendre;
+/// for p in [3, 5, 7, 11, 13, 101] {
+///     let squares: Vec<_> = (1..p).map(|x| x * x % p).collect();
+///     for a in 1..p {
+///         assert_eq!(legendre(a, p) == 1, squares.contains(&a));
+///     }
+///     assert_eq!(legendre(p, p), 0);
+/// }
+/// ```
+#[must_use]
+pub fn legendre(a: u64, p: u64) -> i8 {
+    jacobi(a, p)
+}
+
+/// A square root of a modulo the prime p, by the Tonelli-Shanks algorithm, or `None` if a is not
+/// a square mod p.
+///
+/// Of the two roots r and p - r, the smaller is returned.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::sqrt_mod;
+/// assert_eq!(sqrt_mod(10, 13), Some(6));
+/// assert_eq!(sqrt_mod(5, 13), None);
+/// assert_eq!(sqrt_mod(0, 13), Some(0));
+/// assert_eq!(sqrt_mod(1_030_225, 1_000_000_009), Some(1_015));
+/// ```
+/// ```
+/// # use pj_euler::utils::number_theory::sqrt_mod;
+/// for p in [3, 5, 13, 17, 41, 97, 257] {
+///     assert!((0..p).all(|x| sqrt_mod(x * x % p, p) == Some(x.min(p - x))));
+/// }
+///
+/// // 119 * 2^23 + 1 is prime, and has a large power of 2 in p - 1
+/// let p = 998_244_353;
+/// for x in [2, 3, 1_000_000, 998_244_000] {
+///     let r = sqrt_mod(x * x % p, p).unwrap();
+///     assert!(r == x.min(p - x));
+/// }
+/// ```
+#[must_use]
+pub fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if p == 2 || a == 0 {
+        return Some(a);
+    }
+    if legendre(a, p) != 1 {
+        return None;
+    }
+
+    let r = if p % 4 == 3 {
+        pow_mod(a, (p + 1) / 4, p)
+    } else {
+        // write p - 1 = q 2^s, and find a nonresidue z
+        let s = (p - 1).trailing_zeros();
+        let q = (p - 1) >> s;
+        let z = (2..)
+            .find(|&z| legendre(z, p) == -1)
+            .expect("half of 1..p are nonresidues");
+
+        // maintain r^2 = at mod p, where t has order 2^i for some i < m
+        let (mut m, mut c) = (s, pow_mod(z, q, p));
+        let (mut t, mut r) = (pow_mod(a, q, p), pow_mod(a, q.div_ceil(2), p));
+        while t != 1 {
+            let mut i = 0;
+            let mut t2i = t;
+            while t2i != 1 {
+                t2i = mul_mod(t2i, t2i, p);
+                i += 1;
+            }
+
+            let b = pow_mod(c, 1 << (m - i - 1), p);
+            m = i;
+            c = mul_mod(b, b, p);
+            t = mul_mod(t, c, p);
+            r = mul_mod(r, b, p);
+        }
+        r
+    };
+    Some(r.min(p - r))
+}
+
+/// The least `x >= 0` with `g^x = h mod m`, by baby-step giant-step, or `None` if there is none.
+///
+/// This takes `O(sqrt(m))` time and space. While g shares a factor d with m, any solution has
+/// d dividing h, and we divide d out of g^x = h, leaving `(g/d) g^(x-1) = h/d mod m/d`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::{discrete_log, pow_mod};
+/// assert_eq!(discrete_log(3, 13, 17), Some(4));
+/// assert_eq!(discrete_log(2, 1, 7), Some(0));
+/// assert_eq!(discrete_log(2, 3, 7), None); // 2 generates {1, 2, 4}
+/// assert_eq!(discrete_log(5, pow_mod(5, 123_456_789, 1_000_000_007), 1_000_000_007),
+///     Some(123_456_789));
+/// ```
+/// ```
+/// # use pj_euler::utils::number_theory::{discrete_log, pow_mod};
+/// for (g, m) in [(2, 15), (2, 21), (2, 23), (2, 25), (2, 8), (2, 24), (6, 36), (10, 48)] {
+///     for h in 0..m {
+///         let brute = (0..m).find(|&x| pow_mod(g, x, m) == h);
+///         assert_eq!(discrete_log(g, h, m), brute);
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn discrete_log(g: u64, h: u64, m: u64) -> Option<u64> {
+    let (mut g, mut h, mut m) = (g % m, h % m, m);
+    if h == 1 % m {
+        return Some(0);
+    }
+
+    // solve c g^(x - shift) = h instead, with c the product of the factors divided out of g
+    let (mut c, mut shift) = (1 % m, 0);
+    loop {
+        let d = g.gcd(&m);
+        if d == 1 {
+            break;
+        }
+        if h % d != 0 {
+            return None;
+        }
+        (h, m) = (h / d, m / d);
+        c = mul_mod(c, g / d, m);
+        g %= m;
+        shift += 1;
+        if c == h % m {
+            return Some(shift);
+        }
+    }
+    let n = m.isqrt() + 1;
+
+    // baby steps: h g^j for j < n, keeping the largest j for each value
+    let mut baby = HashMap::with_capacity(n as usize);
+    let mut hgj = h;
+    for j in 0..n {
+        baby.insert(hgj, j);
+        hgj = mul_mod(hgj, g, m);
+    }
+
+    // giant steps: c g^(in) = h g^j, so x = in - j, which is smallest for the first i found
+    let gn = pow_mod(g, n, m);
+    let mut gin = c;
+    for i in 1..=n {
+        gin = mul_mod(gin, gn, m);
+        if let Some(&j) = baby.get(&gin) {
+            return Some(i * n - j + shift);
+        }
+    }
+    None
+}
+
+/// Euler's totient of n, and the prime factors of the totient, from the factorization of n.
+fn totient_and_its_factors(n: u64) -> (u64, Vec<u64>) {
+    let phi = fast_totient(n);
+    let factors = PrimeFactorization::fast_of(phi)
+        .map(|PrimeFactor { factor, .. }| factor)
+        .collect();
+    (phi, factors)
+}
+
+/// The multiplicative order of a modulo n, the least `k > 0` with `a^k = 1 mod n`, or `None` if
+/// a is not coprime to n or n is 0.
+///
+/// The order divides φ(n), so we start there and divide out each prime factor of φ(n) for as long
+/// as the result is still a multiple of the order.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::multiplicative_order;
+/// assert_eq!(multiplicative_order(2, 7), Some(3));
+/// assert_eq!(multiplicative_order(3, 7), Some(6));
+/// assert_eq!(multiplicative_order(4, 6), None);
+/// assert_eq!(multiplicative_order(10, 1), Some(1));
+/// assert_eq!(multiplicative_order(1, 0), None);
+/// ```
+/// ```
+/// # use pj_euler::utils::number_theory::multiplicative_order;
+/// // the decimal expansion of 1/d has a cycle of length ord_d(10), once factors of 2 and 5 are
+/// // removed; 1/983 has the longest cycle for d < 1000
+/// let longest = (2..1000).max_by_key(|&d| {
+///     let mut d = d;
+///     while d % 2 == 0 { d /= 2 }
+///     while d % 5 == 0 { d /= 5 }
+///     multiplicative_order(10, d).unwrap()
+/// });
+/// assert_eq!(longest, Some(983));
+/// ```
+#[must_use]
+pub fn multiplicative_order(a: u64, n: u64) -> Option<u64> {
+    if n == 0 || a.gcd(&n) != 1 {
+        return None;
+    }
+
+    let (phi, factors) = totient_and_its_factors(n);
+    let mut order = phi;
+    for q in factors {
+        while order % q == 0 && pow_mod(a, order / q, n) == 1 % n {
+            order /= q;
+        }
+    }
+    Some(order)
+}
+
+/// The least primitive root modulo n, i.e. a generator of the multiplicative group, or `None` if
+/// the group isn't cyclic or n is 0.
+///
+/// Primitive roots exist exactly when n is 1, 2, 4, `p^k`, or `2p^k` for an odd prime p; then g
+/// is a primitive root iff `g^(φ(n)/q) != 1` for each prime q dividing φ(n).
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::number_theory::primitive_root;
+/// assert_eq!(primitive_root(7), Some(3));
+/// assert_eq!(primitive_root(1_000_000_007), Some(5));
+/// assert_eq!(primitive_root(998_244_353), Some(3));
+/// assert_eq!(primitive_root(25), Some(2));
+/// assert_eq!(primitive_root(18), Some(5));
+/// assert_eq!(primitive_root(8), None);
+/// assert_eq!(primitive_root(15), None);
+/// assert_eq!(primitive_root(0), None);
+/// ```
+/// ```
+/// # use pj_euler::utils::number_theory::{multiplicative_order, primitive_root};
+/// # use pj_euler::utils::primes::totient;
+/// for n in 1..200 {
+///     let brute = (1..=n).find(|&g| multiplicative_order(g, n) == Some(totient(n)));
+///     assert_eq!(primitive_root(n), brute.map(|g| g % n));
+/// }
+/// ```
+#[must_use]
+pub fn primitive_root(n: u64) -> Option<u64> {
+    if n == 0 {
+        return None;
+    }
+
+    let odd_part = n >> n.trailing_zeros();
+    let cyclic =
+        n <= 4 || (!n.is_multiple_of(4) && PrimeFactorization::fast_of(odd_part).count() == 1);
+    if !cyclic {
+        return None;
+    }
+
+    let (phi, factors) = totient_and_its_factors(n);
+    (1..=n)
+        .map(|g| g % n)
+        .filter(|g| g.gcd(&n) == 1)
+        .find(|&g| factors.iter().all(|q| pow_mod(g, phi / q, n) != 1 % n))
+}
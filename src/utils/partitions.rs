@@ -0,0 +1,164 @@
+//! Counting integer partitions, the ways of writing n as a sum of positive integers regardless of
+//! order.
+//!
+//! Everything here is generic over `num::Num`, so counts can be exact with `u128` or `BigUint`, or
+//! reduced with [`ModInt`](super::modular::ModInt) when only p(n) mod m is needed.
+
+use num::Num;
+
+use super::num_fn::{_0, _1};
+use super::seqs::GeneralizedPentagonal;
+
+/// The partition numbers p(k) for every k up to n.
+///
+/// By Euler's pentagonal number theorem,
+///
+/// ```text
+/// p(k) = p(k - 1) + p(k - 2) - p(k - 5) - p(k - 7) + p(k - 12) + p(k - 15) - ...
+/// ```
+///
+/// over the generalized pentagonal numbers, with signs in the pattern `+ + - -`. There are
+/// `O(sqrt(k))` terms, so this takes `O(n^(3/2))` time. The positive and negative terms are summed
+/// separately, so unsigned types work too.
+///
+/// # Examples
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::partitions::partitions_up_to;
+/// let p = partitions_up_to::<u64>(100);
+/// assert_eq!(p[..8], [1, 1, 2, 3, 5, 7, 11, 15]);
+/// assert_eq!(p[100], 190_569_292);
+///
+/// let p = partitions_up_to::<BigUint>(1000);
+/// assert_eq!(p[1000].to_string(), "24061467864032622473692149727991");
+/// ```
+/// ```
+/// # use pj_euler::utils::modular::ModInt;
+/// # use pj_euler::utils::partitions::partitions_up_to;
+/// type Mod = ModInt<1_000_007>;
+/// let exact = partitions_up_to::<u128>(400);
+/// let reduced = partitions_up_to::<Mod>(400);
+/// assert!((0..=400).all(|k| reduced[k].value() as u128 == exact[k] % 1_000_007));
+/// ```
+#[must_use]
+pub fn partitions_up_to<N: Num + Clone>(n: usize) -> Vec<N> {
+    let mut p: Vec<N> = Vec::with_capacity(n + 1);
+    p.push(_1());
+    for k in 1..=n {
+        let (mut plus, mut minus) = (_0::<N>(), _0::<N>());
+        let pentagonal = GeneralizedPentagonal::<usize>::new()
+            .skip(1)
+            .take_while(|&g| g <= k);
+        for (i, g) in pentagonal.enumerate() {
+            if i % 4 < 2 {
+                plus = plus + p[k - g].clone();
+            } else {
+                minus = minus + p[k - g].clone();
+            }
+        }
+        p.push(plus - minus);
+    }
+    p
+}
+
+/// The numbers of partitions of each k up to n into distinct parts.
+///
+/// Each part is either used once or not at all, so we add the parts one at a time, like a 0/1
+/// knapsack.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::partitions::distinct_partitions_up_to;
+/// let q = distinct_partitions_up_to::<u64>(100);
+/// assert_eq!(q[..11], [1, 1, 1, 2, 2, 3, 4, 5, 6, 8, 10]);
+/// assert_eq!(q[100], 444_793);
+/// ```
+#[must_use]
+pub fn distinct_partitions_up_to<N: Num + Clone>(n: usize) -> Vec<N> {
+    let mut q = vec![_0::<N>(); n + 1];
+    q[0] = _1();
+    for part in 1..=n {
+        // go downwards so that this part is counted at most once
+        for k in (part..=n).rev() {
+            q[k] = q[k].clone() + q[k - part].clone();
+        }
+    }
+    q
+}
+
+/// The numbers of partitions of each k up to n into parts from the given set, each usable any
+/// number of times.
+///
+/// Parts are added one at a time, like an unbounded knapsack, so this takes `O(n * parts)` time.
+///
+/// # Panics
+/// Panics if a part is 0.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::partitions::partitions_into_up_to;
+/// # use pj_euler::utils::primes::Primes;
+/// // making change for £2 from British coins
+/// let coins = [1, 2, 5, 10, 20, 50, 100, 200];
+/// assert_eq!(partitions_into_up_to::<u64>(200, &coins)[200], 73_682);
+///
+/// // sums of primes
+/// let primes: Vec<usize> = Primes::up_to(100).collect();
+/// let ways = partitions_into_up_to::<u64>(100, &primes);
+/// assert_eq!(ways[10], 5); // 7 + 3, 5 + 5, 5 + 3 + 2, 3 + 3 + 2 + 2, 2 + 2 + 2 + 2 + 2
+/// assert_eq!((0..=100).find(|&k| ways[k] > 5000), Some(71));
+/// ```
+#[must_use]
+pub fn partitions_into_up_to<N: Num + Clone>(n: usize, parts: &[usize]) -> Vec<N> {
+    let mut ways = vec![_0::<N>(); n + 1];
+    ways[0] = _1();
+    for &part in parts {
+        assert!(part > 0, "parts are positive");
+        // go upwards so that this part can be used again
+        for k in part..=n {
+            ways[k] = ways[k].clone() + ways[k - part].clone();
+        }
+    }
+    ways
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{distinct_partitions_up_to, partitions_into_up_to, partitions_up_to};
+
+    #[test]
+    fn pentagonal_theorem_agrees_with_knapsack() {
+        let parts: Vec<usize> = (1..=500).collect();
+        assert_eq!(
+            partitions_up_to::<u128>(500),
+            partitions_into_up_to::<u128>(500, &parts)
+        );
+    }
+
+    #[test]
+    fn distinct_parts_equal_odd_parts() {
+        let odd: Vec<usize> = (1..=500).step_by(2).collect();
+        assert_eq!(
+            distinct_partitions_up_to::<u128>(500),
+            partitions_into_up_to::<u128>(500, &odd)
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::partitions_up_to;
+    use crate::utils::modular::ModInt;
+
+    extern crate test;
+    use test::Bencher;
+
+    #[bench]
+    fn first_partition_number_divisible_by_a_million(b: &mut Bencher) {
+        type Mod = ModInt<1_000_000>;
+        b.iter(|| {
+            let p = partitions_up_to::<Mod>(60_000);
+            assert_eq!(p.iter().position(|&p| p == Mod::new(0)), Some(55_374));
+        });
+    }
+}
@@ -1,13 +1,42 @@
 //! Utilities relating to prime numbers.
 
+mod counting;
+mod divisors;
+mod miller_rabin;
+mod multiplicative;
+mod rho;
+mod segmented;
+mod sieve;
+mod spf;
+
 use std::iter::Step;
+use std::marker::PhantomData;
+
+use itertools::Itertools;
+use num::{Num, NumCast};
+
+use sieve::Sieve;
 
-use num::Num;
+pub use counting::{prime_count, prime_sum};
+pub use divisors::{aliquot_sum, divisors, sigma_k};
+pub use miller_rabin::{is_probable_prime, miller_rabin};
+pub use multiplicative::{
+    big_omega, fast_totient, mobius, mobius_up_to, multiplicative_up_to, omega, radical, totient,
+    totients_up_to,
+};
+pub use segmented::SegmentedPrimes;
+pub use spf::SpfTable;
 
 use super::num_fn::{_0, _1, _2, _3};
 
+/// Numbers below this are checked for primality by trial division rather than Miller-Rabin.
+const TRIAL_DIVISION_BOUND: u128 = 1 << 20;
+
 /// Check whether n is prime.
 ///
+/// Small numbers are checked by trial division, and large numbers by [`is_probable_prime`], which
+/// is deterministic for anything that fits in a `u64`.
+///
 /// # Examples
 /// ```
 /// # use pj_euler::utils::primes::is_prime;
@@ -16,9 +45,32 @@ use super::num_fn::{_0, _1, _2, _3};
 /// assert!(!is_prime(6));
 /// assert!(!is_prime(9));
 /// assert!(is_prime(17));
+/// assert!(is_prime(1_000_000_000_000_000_003_u64));
 /// ```
 #[must_use]
 pub fn is_prime<N>(n: N) -> bool
+where
+    N: Num + NumCast + Step + PartialOrd + Copy,
+{
+    match num::cast(n) {
+        Some(m) if m >= TRIAL_DIVISION_BOUND => is_probable_prime(m),
+        _ => is_prime_by_trial_division(n),
+    }
+}
+
+/// Check whether n is prime by trial division.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::is_prime_by_trial_division;
+/// assert!(is_prime_by_trial_division(2));
+/// assert!(is_prime_by_trial_division(5));
+/// assert!(!is_prime_by_trial_division(6));
+/// assert!(!is_prime_by_trial_division(9));
+/// assert!(is_prime_by_trial_division(17));
+/// ```
+#[must_use]
+pub fn is_prime_by_trial_division<N>(n: N) -> bool
 where
     N: Num + Step + PartialOrd + Copy,
 {
@@ -41,7 +93,14 @@ where
     true
 }
 
-/// An iterator of primes.
+/// An iterator of primes, backed by a sieve of Eratosthenes.
+///
+/// An iterator made with [`Primes::new`] is unbounded: whenever it runs off the end of its sieve,
+/// the sieve is doubled in size. An iterator made with [`Primes::up_to`] sieves exactly once and
+/// stops at its bound.
+///
+/// # Panics
+/// Panics if the next prime does not fit in `T`.
 ///
 /// # Examples
 /// ```
@@ -59,15 +118,54 @@ where
 /// assert_eq!(p.nth(999).expect("there are infinitely many primes"), 7919);
 /// ```
 pub struct Primes<T> {
-    computed: Vec<T>,
+    sieve: Sieve,
+
+    // The next number to check for primality.
+    next: usize,
+
+    // The largest number we may yield, if any.
+    bound: Option<usize>,
+
+    _type: PhantomData<T>,
 }
 
+/// The size of the first sieve made by an unbounded iterator.
+const INITIAL_SIEVE: usize = 1 << 10;
+
 impl<T> Primes<T> {
-    /// Make a new primes iterator.
+    /// Make a new unbounded primes iterator.
     #[must_use]
     pub fn new() -> Self {
         Self {
-            computed: Vec::default(),
+            sieve: Sieve::new(INITIAL_SIEVE),
+            next: 2,
+            bound: None,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<N: NumCast> Primes<N> {
+    /// Make an iterator over the primes less than or equal to `n`.
+    ///
+    /// # Panics
+    /// Panics if `n` does not fit in a `usize`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use pj_euler::utils::primes::Primes;
+    /// assert_eq!(Primes::up_to(20).collect::<Vec<u32>>(), [2, 3, 5, 7, 11, 13, 17, 19]);
+    /// assert_eq!(Primes::up_to(7).last(), Some(7));
+    /// assert_eq!(Primes::<u32>::up_to(1).next(), None);
+    /// ```
+    #[must_use]
+    pub fn up_to(n: N) -> Self {
+        let n: usize = num::cast(n).expect("bound fits in a usize");
+        Self {
+            sieve: Sieve::new(n + 1),
+            next: 2,
+            bound: Some(n),
+            _type: PhantomData,
         }
     }
 }
@@ -78,27 +176,28 @@ impl<T> Default for Primes<T> {
     }
 }
 
-impl<N: Num + Step + PartialOrd + Copy> Iterator for Primes<N> {
+impl<N: NumCast> Iterator for Primes<N> {
     type Item = N;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let out = match self.computed.last() {
-            None => _2(),                  // 2 is the first prime
-            Some(&n) if n == _2() => _3(), // need a special case so we can step by 2
-            Some(&n) => ((n + _2())..)
-                .step_by(2)
-                .find(|&p| {
-                    self.computed
-                        .iter()
-                        .copied()
-                        .take_while(|&x| x * x <= p)
-                        .all(|x| p % x != _0())
-                })
-                .expect("there are infinitely many primes"),
+        let p = if self.next == 2 {
+            2 // the sieve only knows about odd numbers
+        } else {
+            loop {
+                if let Some(p) = self.sieve.next_odd_prime(self.next) {
+                    break p;
+                } else if self.bound.is_some() {
+                    return None;
+                }
+                self.sieve.extend_to(2 * self.sieve.bound());
+            }
         };
+        if self.bound.is_some_and(|b| p > b) {
+            return None;
+        }
 
-        self.computed.push(out);
-        Some(out)
+        self.next = if p == 2 { 3 } else { p + 2 };
+        Some(num::cast(p).expect("prime fits in the iterator's type"))
     }
 }
 
@@ -120,14 +219,63 @@ pub struct PrimeFactor<T> {
 /// assert_eq!(pf.next(), None);
 /// ```
 pub struct PrimeFactorization<T> {
-    num: T,
-    factor: T,
+    state: FactorizationState<T>,
+}
+
+/// How the factors of a `PrimeFactorization` are produced.
+enum FactorizationState<T> {
+    /// Found lazily, by dividing out candidate factors in ascending order.
+    TrialDivision { num: T, factor: T },
+
+    /// Already found, and sorted in ascending order.
+    Computed(std::vec::IntoIter<PrimeFactor<T>>),
 }
 
 impl<N: Num> PrimeFactorization<N> {
     /// Create a prime factorization.
     pub fn of(num: N) -> Self {
-        Self { num, factor: _2() }
+        Self {
+            state: FactorizationState::TrialDivision { num, factor: _2() },
+        }
+    }
+}
+
+impl<N: Num + NumCast + Copy> PrimeFactorization<N> {
+    /// Create a prime factorization by trial division of small factors, Miller-Rabin, and
+    /// Pollard-Brent rho, which is much faster than [`PrimeFactorization::of`] for numbers with
+    /// large prime factors.
+    ///
+    /// # Panics
+    /// Panics if `num` does not fit in a `u64`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use pj_euler::utils::primes::{PrimeFactor, PrimeFactorization};
+    /// let mut pf = PrimeFactorization::<u64>::fast_of(999_999_866_000_004_473);
+    /// assert_eq!(pf.next(), Some(PrimeFactor { factor: 999_999_929, exponent: 1 }));
+    /// assert_eq!(pf.next(), Some(PrimeFactor { factor: 999_999_937, exponent: 1 }));
+    /// assert_eq!(pf.next(), None);
+    /// ```
+    /// ```
+    /// # use pj_euler::utils::primes::PrimeFactorization;
+    /// assert!((1..10_000_u32)
+    ///     .all(|n| PrimeFactorization::fast_of(n).eq(PrimeFactorization::of(n))));
+    /// ```
+    pub fn fast_of(num: N) -> Self {
+        let mut factors = rho::factorize(num::cast(num).expect("number fits in a u64"));
+        factors.sort_unstable();
+
+        let factors: Vec<_> = factors
+            .into_iter()
+            .dedup_with_count()
+            .map(|(exponent, factor)| PrimeFactor {
+                factor: num::cast(factor).expect("factor is at most the original number"),
+                exponent: num::cast(exponent).expect("exponent is at most the original number"),
+            })
+            .collect();
+        Self {
+            state: FactorizationState::Computed(factors.into_iter()),
+        }
     }
 }
 
@@ -135,29 +283,31 @@ impl<N: Num + PartialOrd + Copy> Iterator for PrimeFactorization<N> {
     type Item = PrimeFactor<N>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.num <= _1() {
-            None
-        } else {
-            // find the next prime factor of self.num
-            while self.num % self.factor != _0() {
-                if self.factor == _2() {
-                    self.factor = self.factor + _1();
-                } else {
-                    self.factor = self.factor + _2();
+        match &mut self.state {
+            FactorizationState::Computed(factors) => factors.next(),
+            FactorizationState::TrialDivision { num, .. } if *num <= _1() => None,
+            FactorizationState::TrialDivision { num, factor } => {
+                // find the next prime factor of num
+                while *num % *factor != _0() {
+                    if *factor == _2() {
+                        *factor = *factor + _1();
+                    } else {
+                        *factor = *factor + _2();
+                    }
                 }
-            }
 
-            // compute the corresponding exponent
-            let mut exponent = _1();
-            self.num = self.num / self.factor;
-            while self.num % self.factor == _0() {
-                self.num = self.num / self.factor;
-                exponent = exponent + _1();
+                // compute the corresponding exponent
+                let mut exponent = _1();
+                *num = *num / *factor;
+                while *num % *factor == _0() {
+                    *num = *num / *factor;
+                    exponent = exponent + _1();
+                }
+                Some(PrimeFactor {
+                    factor: *factor,
+                    exponent,
+                })
             }
-            Some(PrimeFactor {
-                factor: self.factor,
-                exponent,
-            })
         }
     }
 }
@@ -177,14 +327,40 @@ impl<N: Num + PartialOrd + Copy> Iterator for PrimeFactorization<N> {
 /// assert_eq!(number_of_divisors(1), 1);
 /// ```
 pub fn number_of_divisors<N: Num + PartialOrd + Copy>(of: N) -> N {
-    PrimeFactorization::of(of)
+    count_divisors(PrimeFactorization::of(of))
+}
+
+/// Determine the total number of divisors the number, factoring it with a [`SpfTable`].
+///
+/// # Panics
+/// Panics if the number is larger than the table's bound.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{number_of_divisors, number_of_divisors_with, SpfTable};
+/// let spf = SpfTable::new(1000);
+/// assert_eq!(number_of_divisors_with(28, &spf), 6);
+/// assert!((1..=1000).all(|n| number_of_divisors_with(n, &spf) == number_of_divisors(n)));
+/// ```
+pub fn number_of_divisors_with<N: Num + NumCast + PartialOrd + Copy>(of: N, table: &SpfTable) -> N {
+    count_divisors(table.factorize(of))
+}
+
+/// Determine the total number of divisors of the number with the given prime factorization.
+fn count_divisors<N: Num + Copy>(factors: PrimeFactorization<N>) -> N
+where
+    PrimeFactorization<N>: Iterator<Item = PrimeFactor<N>>,
+{
+    factors
         .map(|PrimeFactor { exponent, .. }| exponent + _1())
         .fold(_1(), |p, n| p * n)
 }
 
 #[cfg(test)]
 mod benches {
-    use super::{is_prime, PrimeFactorization, Primes};
+    use super::{
+        is_prime, miller_rabin, prime_count, PrimeFactorization, Primes, SegmentedPrimes, SpfTable,
+    };
 
     extern crate test;
     use test::Bencher;
@@ -198,6 +374,14 @@ mod benches {
         });
     }
 
+    #[bench]
+    fn miller_rabin_eighteen_digits(b: &mut Bencher) {
+        b.iter(|| {
+            let n = 1_000_000_000_000_000_000_u64;
+            assert_eq!((n..n + 1000).filter(|&n| miller_rabin(n)).count(), 23);
+        });
+    }
+
     #[bench]
     fn thousanth_prime(b: &mut Bencher) {
         b.iter(|| {
@@ -205,8 +389,60 @@ mod benches {
         });
     }
 
+    #[bench]
+    fn sum_primes_below_ten_million(b: &mut Bencher) {
+        b.iter(|| {
+            assert_eq!(
+                Primes::<u64>::up_to(10_000_000).sum::<u64>(),
+                3_203_324_994_356
+            );
+        });
+    }
+
+    #[bench]
+    fn prime_count_ten_to_ten(b: &mut Bencher) {
+        b.iter(|| assert_eq!(prime_count(10_000_000_000), 455_052_511));
+    }
+
+    #[bench]
+    fn primes_near_ten_to_twelve(b: &mut Bencher) {
+        b.iter(|| {
+            let lo = 1_000_000_000_000_u64;
+            assert_eq!(SegmentedPrimes::new(lo..lo + 1_000_000).count(), 36_249);
+        });
+    }
+
     #[bench]
     fn factorize_large_n(b: &mut Bencher) {
         b.iter(|| for _factor in PrimeFactorization::of(6_002_462) {});
     }
+
+    #[bench]
+    fn factorize_range(b: &mut Bencher) {
+        b.iter(|| {
+            for n in 1..10_000_u32 {
+                for _factor in PrimeFactorization::of(n) {}
+            }
+        });
+    }
+
+    #[bench]
+    fn factorize_range_with_spf_table(b: &mut Bencher) {
+        b.iter(|| {
+            let spf = SpfTable::new(10_000);
+            for n in 1..10_000_u32 {
+                for _factor in spf.factorize(n) {}
+            }
+        });
+    }
+
+    #[bench]
+    fn fast_factorize_semiprime(b: &mut Bencher) {
+        b.iter(|| {
+            assert_eq!(
+                PrimeFactorization::fast_of(999_999_866_000_004_473_u64).count(),
+                2
+            );
+        });
+    }
 }
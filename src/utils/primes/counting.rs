@@ -0,0 +1,136 @@
+//! Sublinear prime counting and summing.
+
+use num::Num;
+
+use crate::utils::num_fn::_0;
+
+/// The number of primes less than or equal to x, π(x).
+///
+/// This uses Lucy_Hedgehog's algorithm, which runs in `O(x^(3/4))` time and `O(x^(1/2))` space.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{prime_count, Primes};
+/// assert_eq!(prime_count(1), 0);
+/// assert_eq!(prime_count(2), 1);
+/// assert_eq!(prime_count(100), 25);
+/// assert!((0..2000).all(|x| prime_count(x) == Primes::<u64>::up_to(x).count() as u64));
+/// ```
+#[must_use]
+pub fn prime_count(x: u64) -> u64 {
+    lucy(x, |v| v.saturating_sub(1), |_| 1)
+}
+
+/// The sum of the primes less than or equal to x.
+///
+/// This uses the same algorithm as [`prime_count`], weighting each prime by itself.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{prime_sum, Primes};
+/// assert_eq!(prime_sum(1), 0);
+/// assert_eq!(prime_sum(10), 17);
+/// assert_eq!(prime_sum(2_000_000), 142_913_828_922);
+/// assert!((0..2000).all(|x| prime_sum(x) == Primes::<u128>::up_to(x as u128).sum()));
+/// ```
+#[must_use]
+pub fn prime_sum(x: u64) -> u128 {
+    lucy(
+        x,
+        |v| (u128::from(v) * (u128::from(v) + 1) / 2).saturating_sub(1),
+        u128::from,
+    )
+}
+
+/// Compute the sum of `weight(p)` over primes `p <= x`.
+///
+/// `initial(v)` must be the sum of `weight(n)` for `2 <= n <= v`, and `weight` must be completely
+/// multiplicative. We track `S(v)`, the sum over numbers up to `v` which are prime or have no
+/// prime factor below the current `p`, for each of the `O(sqrt(x))` distinct values `v = x / i`;
+/// sieving out each prime `p` updates every `S(v)` with `v >= p^2` by
+///
+/// ```text
+/// S(v) -= weight(p) * (S(v / p) - S(p - 1))
+/// ```
+fn lucy<T: Num + Copy>(x: u64, initial: impl Fn(u64) -> T, weight: impl Fn(u64) -> T) -> T {
+    if x < 2 {
+        return _0();
+    }
+    let r = x.isqrt();
+
+    // small[v] = S(v) for v <= r, and large[i] = S(x / i) for i <= r
+    let mut small: Vec<T> = (0..=r).map(&initial).collect();
+    let mut large: Vec<T> = (0..=r)
+        .map(|i| x.checked_div(i).map_or_else(_0, &initial))
+        .collect();
+
+    for p in 2..=r {
+        if small[p as usize] == small[p as usize - 1] {
+            continue; // p was sieved out by a smaller prime
+        }
+        let sp = small[p as usize - 1];
+        let w = weight(p);
+        let p2 = p * p;
+
+        // x / i >= p^2 exactly when i <= x / p^2
+        for i in 1..=r.min(x / p2) {
+            let d = i * p;
+            let s = if d <= r {
+                large[d as usize]
+            } else {
+                small[(x / d) as usize]
+            };
+            large[i as usize] = large[i as usize] - w * (s - sp);
+        }
+
+        // update in descending order so that small[v / p] is still the old value
+        for v in (p2..=r).rev() {
+            small[v as usize] = small[v as usize] - w * (small[(v / p) as usize] - sp);
+        }
+    }
+
+    large[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{prime_count, prime_sum};
+
+    #[test]
+    fn prime_count_powers_of_ten() {
+        let known = [
+            4,
+            25,
+            168,
+            1229,
+            9592,
+            78498,
+            664_579,
+            5_761_455,
+            50_847_534,
+            455_052_511,
+        ];
+        for (k, pi) in (1..).zip(known) {
+            assert_eq!(prime_count(10_u64.pow(k)), pi);
+        }
+    }
+
+    #[test]
+    fn prime_sum_powers_of_ten() {
+        let known = [
+            17,
+            1060,
+            76127,
+            5_736_396,
+            454_396_537,
+            37_550_402_023,
+            3_203_324_994_356,
+            279_209_790_387_276,
+            24_739_512_092_254_535,
+            2_220_822_432_581_729_238,
+        ];
+        for (k, sum) in (1..).zip(known) {
+            assert_eq!(prime_sum(10_u64.pow(k)), sum);
+        }
+    }
+}
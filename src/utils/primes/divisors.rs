@@ -0,0 +1,94 @@
+//! Functions of the divisors of a number.
+
+use num::Num;
+
+use super::{PrimeFactor, PrimeFactorization};
+use crate::utils::num_fn::{_0, _1};
+
+/// An iterator over the divisors of n in ascending order.
+///
+/// # Panics
+/// Panics if n is 0, which every number divides.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::divisors;
+/// assert_eq!(divisors(28).collect::<Vec<_>>(), [1, 2, 4, 7, 14, 28]);
+/// assert_eq!(divisors(1).collect::<Vec<_>>(), [1]);
+/// assert_eq!(divisors(13).collect::<Vec<_>>(), [1, 13]);
+/// ```
+/// ```
+/// # use pj_euler::utils::primes::{divisors, number_of_divisors};
+/// assert!((1..1000).all(|n| divisors(n).count() == number_of_divisors(n)));
+/// assert!((1..1000).all(|n| divisors(n).eq((1..=n).filter(|d| n % d == 0))));
+/// ```
+pub fn divisors<N: Num + PartialOrd + Copy>(n: N) -> impl Iterator<Item = N> {
+    assert!(n != _0(), "n is positive");
+    let mut out: Vec<N> = vec![_1()];
+    for PrimeFactor { factor, exponent } in PrimeFactorization::of(n) {
+        // multiply each divisor found so far by each power of this factor
+        let len = out.len();
+        let (mut power, mut i) = (factor, _1::<N>());
+        while i <= exponent {
+            for j in 0..len {
+                out.push(out[j] * power);
+            }
+            power = power * factor;
+            i = i + _1();
+        }
+    }
+
+    out.sort_unstable_by(|a, b| a.partial_cmp(b).expect("numbers are totally ordered"));
+    out.into_iter()
+}
+
+/// The sum of the kth powers of the divisors of n.
+///
+/// # Panics
+/// Panics if n is 0.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::sigma_k;
+/// assert_eq!(sigma_k(12, 0), 6);
+/// assert_eq!(sigma_k(12, 1), 1 + 2 + 3 + 4 + 6 + 12);
+/// assert_eq!(sigma_k(12, 2), 1 + 4 + 9 + 16 + 36 + 144);
+/// ```
+/// ```
+/// # use pj_euler::utils::primes::{divisors, sigma_k};
+/// assert!((1..1000_u64).all(|n| sigma_k(n, 3) == divisors(n).map(|d| d.pow(3)).sum()));
+/// ```
+pub fn sigma_k<N: Num + PartialOrd + Copy>(n: N, k: usize) -> N {
+    assert!(n != _0(), "n is positive");
+    PrimeFactorization::of(n)
+        .map(|PrimeFactor { factor, exponent }| {
+            // 1 + p^k + p^2k + ... + p^ek
+            let step = num::pow(factor, k);
+            let (mut sum, mut power, mut i) = (_1(), _1(), _0::<N>());
+            while i < exponent {
+                power = power * step;
+                sum = sum + power;
+                i = i + _1();
+            }
+            sum
+        })
+        .fold(_1(), |p, n| p * n)
+}
+
+/// The sum of the proper divisors of n, i.e. every divisor except n itself.
+///
+/// # Panics
+/// Panics if n is 0.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::aliquot_sum;
+/// assert_eq!(aliquot_sum(1), 0);
+/// assert_eq!(aliquot_sum(13), 1);
+/// assert_eq!(aliquot_sum(28), 28); // perfect
+/// assert_eq!(aliquot_sum(220), 284); // amicable
+/// assert_eq!(aliquot_sum(284), 220);
+/// ```
+pub fn aliquot_sum<N: Num + PartialOrd + Copy>(n: N) -> N {
+    sigma_k(n, 1) - n
+}
@@ -0,0 +1,158 @@
+//! Miller-Rabin primality testing.
+
+use crate::utils::modular::Montgomery;
+
+/// Witnesses which make Miller-Rabin deterministic for every `u64`, due to Jim Sinclair.
+const U64_WITNESSES: [u64; 7] = [2, 325, 9375, 28178, 450_775, 9_780_504, 1_795_265_022];
+
+/// The first twenty primes, used as witnesses above `u64::MAX`.
+///
+/// The first thirteen of these suffice for every n below 3.3 * 10^24; there is no known
+/// deterministic set for all of `u128`.
+const U128_WITNESSES: [u128; 20] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
+/// Compute `a + b mod m` without overflowing, for `a, b < m`.
+const fn add_mod_u128(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// Compute `a * b mod m` without overflowing, for `a, b < m`, by double-and-add.
+///
+/// Above `u64` the product no longer fits in a `u128`, so this can't widen like
+/// [`mul_mod`](crate::utils::number_theory::mul_mod).
+const fn mul_mod_u128(mut a: u128, mut b: u128, m: u128) -> u128 {
+    let mut out = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            out = add_mod_u128(out, a, m);
+        }
+        a = add_mod_u128(a, a, m);
+        b >>= 1;
+    }
+    out
+}
+
+/// Check whether odd `n` is a strong probable prime to base `a`, where `n - 1 = d * 2^s`.
+fn strong_probable_prime_u64(mont: &Montgomery, a: u64, d: u64, s: u32) -> bool {
+    let n = mont.modulus();
+    let a = a % n;
+    if a == 0 {
+        return true;
+    }
+
+    // compare in Montgomery form, where -1 is n - R mod n
+    let (one, minus_one) = (mont.one(), n - mont.one());
+    let mut x = mont.pow(mont.to_montgomery(a), d);
+    if x == one || x == minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = mont.mul(x, x);
+        if x == minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/// Check whether odd `n` is a strong probable prime to base `a`, where `n - 1 = d * 2^s`.
+fn strong_probable_prime_u128(n: u128, a: u128, d: u128, s: u32) -> bool {
+    let a = a % n;
+    if a == 0 {
+        return true;
+    }
+
+    // compute x = a^d mod n by square-and-multiply
+    let (mut x, mut base, mut e) = (1, a, d);
+    while e > 0 {
+        if e & 1 == 1 {
+            x = mul_mod_u128(x, base, n);
+        }
+        base = mul_mod_u128(base, base, n);
+        e >>= 1;
+    }
+
+    if x == 1 || x == n - 1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = mul_mod_u128(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// Check whether `n` is prime, deterministically, by the Miller-Rabin test.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{is_prime_by_trial_division, miller_rabin};
+/// assert!((0..10_000).all(|n| miller_rabin(n) == is_prime_by_trial_division(n)));
+///
+/// let n = 1_000_000_000_000;
+/// assert!((n..n + 1000).all(|n| miller_rabin(n) == is_prime_by_trial_division(n)));
+/// ```
+/// ```
+/// # use pj_euler::utils::primes::miller_rabin;
+/// assert!(!miller_rabin(3_215_031_751)); // a strong pseudoprime to bases 2, 3, 5, and 7
+/// assert!(!miller_rabin(3_825_123_056_546_413_051)); // and to every base up to 23
+/// assert!(miller_rabin((1 << 61) - 1));
+/// assert!(miller_rabin(18_446_744_073_709_551_557)); // the largest prime below 2^64
+/// ```
+#[must_use]
+pub fn miller_rabin(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    if n.is_multiple_of(2) {
+        return n == 2;
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mont = Montgomery::new(n);
+    U64_WITNESSES
+        .iter()
+        .all(|&a| strong_probable_prime_u64(&mont, a, d, s))
+}
+
+/// Check whether `n` is prime by the Miller-Rabin test.
+///
+/// This is deterministic below 3.3 * 10^24, which includes every `u64`; above that, a composite
+/// may be reported as prime, although no counterexample is known.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{is_prime_by_trial_division, is_probable_prime};
+/// assert!((0..10_000).all(|n| is_probable_prime(n) == is_prime_by_trial_division(n)));
+/// ```
+/// ```
+/// # use pj_euler::utils::primes::is_probable_prime;
+/// assert!(is_probable_prime((1 << 89) - 1));
+/// assert!(is_probable_prime((1 << 127) - 1));
+/// assert!(!is_probable_prime(((1 << 61) - 1) * ((1 << 67) - 1)));
+/// assert!(!is_probable_prime(18_446_744_073_709_551_557 * 18_446_744_073_709_551_557));
+/// ```
+#[must_use]
+pub fn is_probable_prime(n: u128) -> bool {
+    if let Ok(n) = u64::try_from(n) {
+        return miller_rabin(n);
+    }
+    if n.is_multiple_of(2) {
+        return false;
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    U128_WITNESSES
+        .iter()
+        .all(|&a| strong_probable_prime_u128(n, a, d, s))
+}
@@ -0,0 +1,276 @@
+//! Common arithmetic functions defined by the prime factorization.
+
+use num::{Num, NumCast};
+
+use super::{PrimeFactor, PrimeFactorization, SpfTable};
+use crate::utils::num_fn::{_0, _1};
+
+/// Euler's totient function φ(n), the number of integers in `1..=n` coprime to n.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::totient;
+/// assert_eq!(totient(1), 1);
+/// assert_eq!(totient(9), 6);
+/// assert_eq!(totient(36), 12);
+/// assert_eq!(totient(97), 96);
+/// ```
+pub fn totient<N: Num + PartialOrd + Copy>(n: N) -> N {
+    totient_from_factors(n, PrimeFactorization::of(n))
+}
+
+/// Euler's totient function φ(n), factorizing n with [`PrimeFactorization::fast_of`], which is
+/// much faster than [`totient`] when n has large prime factors.
+///
+/// # Panics
+/// Panics if n does not fit in a `u64`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{fast_totient, totient};
+/// assert_eq!(fast_totient(999_999_866_000_004_473_u64), 999_999_928 * 999_999_936);
+/// assert!((1..10_000_u32).all(|n| fast_totient(n) == totient(n)));
+/// ```
+pub fn fast_totient<N: Num + NumCast + PartialOrd + Copy>(n: N) -> N {
+    totient_from_factors(n, PrimeFactorization::fast_of(n))
+}
+
+/// φ(n), given the prime factors of n.
+fn totient_from_factors<N: Num + Copy>(n: N, factors: impl Iterator<Item = PrimeFactor<N>>) -> N {
+    factors.fold(n, |phi, PrimeFactor { factor, .. }| {
+        phi / factor * (factor - _1())
+    })
+}
+
+/// The Möbius function μ(n): 0 if n has a square factor, otherwise -1 to the number of prime
+/// factors of n.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::mobius;
+/// assert_eq!(mobius(1), 1);
+/// assert_eq!(mobius(2), -1);
+/// assert_eq!(mobius(6), 1);
+/// assert_eq!(mobius(12), 0);
+/// assert_eq!(mobius(30), -1);
+/// ```
+pub fn mobius<N: Num + PartialOrd + Copy>(n: N) -> i8 {
+    let mut out = 1;
+    for PrimeFactor { exponent, .. } in PrimeFactorization::of(n) {
+        if exponent > _1() {
+            return 0;
+        }
+        out = -out;
+    }
+    out
+}
+
+/// The number of distinct prime factors ω(n).
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::omega;
+/// assert_eq!(omega(1), 0);
+/// assert_eq!(omega(12), 2);
+/// assert_eq!(omega(30), 3);
+/// ```
+pub fn omega<N: Num + PartialOrd + Copy>(n: N) -> N {
+    PrimeFactorization::of(n).fold(_0(), |count, _| count + _1())
+}
+
+/// The number of prime factors Ω(n), counted with multiplicity.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::big_omega;
+/// assert_eq!(big_omega(1), 0);
+/// assert_eq!(big_omega(12), 3);
+/// assert_eq!(big_omega(1024), 10);
+/// ```
+pub fn big_omega<N: Num + PartialOrd + Copy>(n: N) -> N {
+    PrimeFactorization::of(n).fold(_0(), |count, PrimeFactor { exponent, .. }| count + exponent)
+}
+
+/// The radical of n, the product of its distinct prime factors.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::radical;
+/// assert_eq!(radical(1), 1);
+/// assert_eq!(radical(504), 42);
+/// assert_eq!(radical(1024), 2);
+/// ```
+pub fn radical<N: Num + PartialOrd + Copy>(n: N) -> N {
+    PrimeFactorization::of(n).fold(_1(), |rad, PrimeFactor { factor, .. }| rad * factor)
+}
+
+/// Compute f(n) for every n up to and including `bound`, where f is the multiplicative function
+/// with the given values on prime powers.
+///
+/// The entry for 0 is 0. The table is built from a [`SpfTable`], so `f` is called once per prime
+/// power, and every other entry is a single multiplication.
+///
+/// # Panics
+/// Panics if `bound` does not fit in a `u32`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{multiplicative_up_to, sigma_k, PrimeFactor};
+/// // the sum of divisors, from sigma(p^k) = 1 + p + ... + p^k
+/// let sigma: Vec<usize> = multiplicative_up_to(1000, |PrimeFactor { factor, exponent }| {
+///     (0..=exponent).map(|i| factor.pow(i as u32)).sum()
+/// });
+/// assert_eq!(sigma[28], 56);
+/// assert!(sigma.into_iter().skip(1).eq((1..=1000).map(|n| sigma_k(n, 1))));
+/// ```
+pub fn multiplicative_up_to<T: Num + Copy>(
+    bound: usize,
+    f: impl Fn(PrimeFactor<usize>) -> T,
+) -> Vec<T> {
+    let spf = SpfTable::new(bound);
+    let mut out = vec![_0(); bound + 1];
+    if bound >= 1 {
+        out[1] = _1();
+    }
+
+    for n in 2..=bound {
+        // split n into a power of its smallest prime factor and the coprime rest
+        let factor = spf.smallest_prime_factor(n).expect("n > 1") as usize;
+        let (mut rest, mut exponent) = (n / factor, 1);
+        while rest % factor == 0 {
+            rest /= factor;
+            exponent += 1;
+        }
+
+        out[n] = if rest == 1 {
+            f(PrimeFactor { factor, exponent })
+        } else {
+            out[rest] * out[n / rest]
+        };
+    }
+    out
+}
+
+/// Compute φ(n) for every n up to and including `bound` with a linear sieve.
+///
+/// The entry for 0 is 0.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{totient, totients_up_to};
+/// assert_eq!(totients_up_to(10), [0, 1, 1, 2, 2, 4, 2, 6, 4, 6, 4]);
+/// assert!(totients_up_to(10_000).into_iter().skip(1).eq((1..=10_000).map(totient)));
+/// ```
+#[must_use]
+pub fn totients_up_to(bound: usize) -> Vec<usize> {
+    let mut phi = vec![0; bound + 1];
+    let mut primes = vec![];
+    if bound >= 1 {
+        phi[1] = 1;
+    }
+
+    for i in 2..=bound {
+        if phi[i] == 0 {
+            phi[i] = i - 1;
+            primes.push(i);
+        }
+
+        // every composite is reached exactly once, from its smallest prime factor p
+        for &p in &primes {
+            if i * p > bound {
+                break;
+            }
+            if i % p == 0 {
+                phi[i * p] = phi[i] * p;
+                break;
+            }
+            phi[i * p] = phi[i] * (p - 1);
+        }
+    }
+    phi
+}
+
+/// Compute μ(n) for every n up to and including `bound` with a linear sieve.
+///
+/// The entry for 0 is 0.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{mobius, mobius_up_to};
+/// assert_eq!(mobius_up_to(10), [0, 1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+/// assert!(mobius_up_to(10_000).into_iter().skip(1).eq((1..=10_000).map(mobius)));
+/// ```
+#[must_use]
+pub fn mobius_up_to(bound: usize) -> Vec<i8> {
+    let mut mu = vec![0; bound + 1];
+    let mut composite = vec![false; bound + 1];
+    let mut primes = vec![];
+    if bound >= 1 {
+        mu[1] = 1;
+    }
+
+    for i in 2..=bound {
+        if !composite[i] {
+            mu[i] = -1;
+            primes.push(i);
+        }
+
+        // every composite is reached exactly once, from its smallest prime factor p
+        for &p in &primes {
+            if i * p > bound {
+                break;
+            }
+            composite[i * p] = true;
+            if i % p == 0 {
+                mu[i * p] = 0;
+                break;
+            }
+            mu[i * p] = -mu[i];
+        }
+    }
+    mu
+}
+
+#[cfg(test)]
+mod tests {
+    use num::integer::gcd;
+
+    use super::{big_omega, mobius, omega, radical, totient};
+    use crate::utils::primes::{divisors, is_prime};
+
+    const BOUND: u32 = 2000;
+
+    #[test]
+    fn totient_counts_coprimes() {
+        for n in 1..BOUND {
+            assert_eq!(
+                totient(n),
+                (1..=n).filter(|&k| gcd(n, k) == 1).count() as u32
+            );
+        }
+    }
+
+    #[test]
+    fn mobius_sums_to_zero_over_divisors() {
+        // sum_{d | n} μ(d) is 1 if n = 1 and 0 otherwise
+        assert_eq!(mobius(1), 1);
+        for n in 2..BOUND {
+            assert_eq!(divisors(n).map(|d| i32::from(mobius(d))).sum::<i32>(), 0);
+        }
+    }
+
+    #[test]
+    fn omegas_count_prime_divisors() {
+        for n in 1..BOUND {
+            let prime_divisors = || divisors(n).filter(|&d| is_prime(d));
+            assert_eq!(omega(n), prime_divisors().count() as u32);
+            assert_eq!(radical(n), prime_divisors().product());
+
+            // count each p^k dividing n
+            let prime_powers = prime_divisors()
+                .flat_map(|p| (1..).map(move |k| p.pow(k)).take_while(|&q| n % q == 0))
+                .count();
+            assert_eq!(big_omega(n), prime_powers as u32);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Integer factorization by Pollard-Brent rho.
+
+use num::integer::gcd;
+
+use super::miller_rabin::miller_rabin;
+use crate::utils::number_theory::mul_mod;
+
+/// Factors below this are found by trial division before falling back to rho.
+const TRIAL_DIVISION_BOUND: u64 = 1 << 10;
+
+/// The number of steps to take between each gcd in Brent's cycle finding.
+const BATCH: u64 = 128;
+
+/// Find the prime factors of `n`, with multiplicity, in no particular order.
+pub fn factorize(mut n: u64) -> Vec<u64> {
+    let mut out = vec![];
+
+    // rho is slow to find small factors, so get rid of those first
+    for p in std::iter::once(2).chain((3..TRIAL_DIVISION_BOUND).step_by(2)) {
+        if p * p > n {
+            break;
+        }
+        while n.is_multiple_of(p) {
+            out.push(p);
+            n /= p;
+        }
+    }
+
+    let mut stack = vec![n];
+    while let Some(n) = stack.pop() {
+        if n <= 1 {
+            continue;
+        } else if miller_rabin(n) {
+            out.push(n);
+        } else {
+            let d = brent(n);
+            stack.push(d);
+            stack.push(n / d);
+        }
+    }
+    out
+}
+
+/// Find a nontrivial factor of odd composite `n` by Pollard's rho algorithm, with Brent's cycle
+/// finding and batched gcds.
+fn brent(n: u64) -> u64 {
+    // if a choice of c finds the trivial factor n, try again with the next c
+    for c in 1.. {
+        let f = |x: u64| ((u128::from(x) * u128::from(x) + c) % u128::from(n)) as u64;
+
+        let (mut x, mut y, mut ys) = (2, 2, 2);
+        let (mut q, mut g) = (1, 1);
+        let mut r = 1;
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = f(y);
+                    q = mul_mod(q, x.abs_diff(y), n);
+                }
+                g = gcd(q, n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+
+        // the batch overshot, so step through it one at a time
+        if g == n {
+            loop {
+                ys = f(ys);
+                g = gcd(x.abs_diff(ys), n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!("some choice of c finds a factor")
+}
@@ -0,0 +1,140 @@
+//! A segmented sieve of Eratosthenes for primes in a window.
+
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use num::NumCast;
+
+use super::Primes;
+
+/// The number of odd numbers sieved at once; small enough that a segment fits in L1 cache.
+const SEGMENT: usize = 1 << 15;
+
+/// An iterator over the primes in a range, found by sieving the range one block at a time.
+///
+/// Only the base primes up to the square root of the end of the range are ever stored, so this
+/// is suitable for narrow windows far from the origin.
+///
+/// # Panics
+/// Panics if the bounds of the range do not fit in a `u64`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::SegmentedPrimes;
+/// let p: Vec<u32> = SegmentedPrimes::new(0..30).collect();
+/// assert_eq!(p, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+/// ```
+/// ```
+/// # use pj_euler::utils::primes::SegmentedPrimes;
+/// let mut p = SegmentedPrimes::<u64>::new(1_000_000_000_000..1_000_000_000_100);
+/// assert_eq!(p.next(), Some(1_000_000_000_039));
+/// assert_eq!(p.next(), Some(1_000_000_000_061));
+/// assert_eq!(p.next(), Some(1_000_000_000_063));
+/// assert_eq!(p.next(), Some(1_000_000_000_091));
+/// assert_eq!(p.next(), None);
+/// ```
+pub struct SegmentedPrimes<T> {
+    // The odd primes up to the square root of `hi`, which all fit in a u32.
+    base: Vec<u32>,
+
+    // The end of the range.
+    hi: u64,
+
+    // Whether 2 is in the range and has not been yielded yet.
+    two: bool,
+
+    // The odd number corresponding to the start of the current segment.
+    start: u64,
+
+    // Whether each odd number in the current segment is composite.
+    composite: Vec<bool>,
+
+    // The index of the next entry of the current segment to check.
+    pos: usize,
+
+    _type: PhantomData<T>,
+}
+
+impl<N: NumCast> SegmentedPrimes<N> {
+    /// Make an iterator over the primes in `range`.
+    #[must_use]
+    pub fn new(range: Range<N>) -> Self {
+        let lo: u64 = num::cast(range.start).expect("range fits in a u64");
+        let hi: u64 = num::cast(range.end).expect("range fits in a u64");
+
+        let root = num::cast(hi.saturating_sub(1).isqrt()).expect("root fits in a u32");
+        let base = Primes::<u32>::up_to(root)
+            .skip(1) // the segments only contain odd numbers
+            .collect();
+
+        // the first segment starts at the first odd number in the range, except 1
+        let start = lo.max(3) | 1;
+
+        let mut out = Self {
+            base,
+            hi,
+            two: lo <= 2 && 2 < hi,
+            start,
+            composite: Vec::with_capacity(SEGMENT),
+            pos: 0,
+            _type: PhantomData,
+        };
+        out.sieve_segment();
+        out
+    }
+}
+
+impl<T> SegmentedPrimes<T> {
+    /// Sieve the odd numbers in `[start, min(start + 2 * SEGMENT, hi))`.
+    fn sieve_segment(&mut self) {
+        let end = self.hi.min(self.start.saturating_add(2 * SEGMENT as u64));
+        self.composite.clear();
+        self.composite
+            .resize(end.saturating_sub(self.start).div_ceil(2) as usize, false);
+        self.pos = 0;
+
+        let base = self.base.iter().map(|&p| p as u64);
+        for p in base.take_while(|&p| p * p < end) {
+            // the first odd multiple of p in the segment which isn't p itself, if it fits
+            let Some(mut m) = self.start.div_ceil(p).checked_mul(p) else {
+                continue;
+            };
+            m = m.max(p * p);
+            if m % 2 == 0 {
+                let Some(odd) = m.checked_add(p) else {
+                    continue;
+                };
+                m = odd;
+            }
+            for m in (m..end).step_by(2 * p as usize) {
+                self.composite[((m - self.start) / 2) as usize] = true;
+            }
+        }
+    }
+}
+
+impl<N: NumCast> Iterator for SegmentedPrimes<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.two {
+            self.two = false;
+            return num::cast(2);
+        }
+
+        loop {
+            if let Some(i) = self.composite[self.pos..].iter().position(|&c| !c) {
+                self.pos += i + 1;
+                let p = self.start + 2 * (self.pos - 1) as u64;
+                return Some(num::cast(p).expect("prime fits in the iterator's type"));
+            }
+
+            // move on to the next segment
+            self.start = self.start.saturating_add(2 * self.composite.len() as u64);
+            if self.start >= self.hi {
+                return None;
+            }
+            self.sieve_segment();
+        }
+    }
+}
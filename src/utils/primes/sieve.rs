@@ -0,0 +1,81 @@
+//! A bit-packed sieve of Eratosthenes over the odd numbers.
+
+/// The number of bits in each word of the sieve.
+const WORD: usize = u64::BITS as usize;
+
+/// A sieve of Eratosthenes which only stores the odd numbers, one bit each.
+///
+/// Bit `i` is set iff `2i + 1` is composite (or is `1`). The sieve can be grown in place, in which
+/// case only the newly covered numbers are sieved.
+pub struct Sieve {
+    bits: Vec<u64>,
+
+    // The sieve is valid for all numbers strictly less than this bound.
+    bound: usize,
+}
+
+impl Sieve {
+    /// Sieve all numbers less than `bound`.
+    pub fn new(bound: usize) -> Self {
+        let mut sieve = Self {
+            bits: vec![1], // 1 is not prime
+            bound: 0,
+        };
+        sieve.extend_to(bound);
+        sieve
+    }
+
+    /// The (exclusive) upper bound of the sieve.
+    pub const fn bound(&self) -> usize {
+        self.bound
+    }
+
+    /// Grow the sieve so that it is valid for all numbers less than `bound`.
+    pub fn extend_to(&mut self, bound: usize) {
+        if bound <= self.bound {
+            return;
+        }
+        let old = self.bound;
+        self.bound = bound;
+        self.bits.resize(bound.div_ceil(2 * WORD).max(1), 0);
+
+        // every composite in [old, bound) has an odd prime factor p with p * p < bound; any such p
+        // which is itself in the new range is fully sieved by smaller primes before we reach it
+        for p in (3..).step_by(2).take_while(|p| p * p < bound) {
+            if !self.is_odd_prime(p) {
+                continue;
+            }
+
+            // the first odd multiple of p which we haven't sieved already
+            let mut m = (p * p).max(old.div_ceil(p) * p);
+            if m % 2 == 0 {
+                m += p;
+            }
+            for m in (m..bound).step_by(2 * p) {
+                self.bits[m / (2 * WORD)] |= 1 << ((m / 2) % WORD);
+            }
+        }
+    }
+
+    /// Check whether the odd number `n` is prime; `n` must be less than the bound.
+    pub fn is_odd_prime(&self, n: usize) -> bool {
+        debug_assert!(n % 2 == 1 && n < self.bound);
+        self.bits[n / (2 * WORD)] & (1 << ((n / 2) % WORD)) == 0
+    }
+
+    /// Find the least odd prime which is at least `n`, if there is one below the bound.
+    pub fn next_odd_prime(&self, n: usize) -> Option<usize> {
+        let mut i = n / 2;
+        while i < self.bound.div_ceil(2) {
+            // skip over whole words of composites at a time
+            let word = !self.bits[i / WORD] >> (i % WORD);
+            if word == 0 {
+                i = (i / WORD + 1) * WORD;
+            } else {
+                i += word.trailing_zeros() as usize;
+                return Some(2 * i + 1).filter(|&p| p < self.bound);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,112 @@
+//! A table of smallest prime factors, for factoring many small numbers.
+
+use num::{Num, NumCast};
+
+use super::{FactorizationState, PrimeFactor, PrimeFactorization};
+
+/// A table of the smallest prime factor of every number up to a bound, built by a linear sieve.
+///
+/// Once built, any number up to the bound can be factored in `O(log n)` by repeatedly dividing
+/// out its smallest prime factor.
+///
+/// # Panics
+/// Panics if the bound does not fit in a `u32`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::primes::{PrimeFactor, SpfTable};
+/// let spf = SpfTable::new(100);
+/// assert_eq!(spf.smallest_prime_factor(91), Some(7));
+/// assert_eq!(spf.smallest_prime_factor(97), Some(97));
+/// assert_eq!(spf.smallest_prime_factor(1), None);
+///
+/// let mut pf = spf.factorize(40_u32);
+/// assert_eq!(pf.next(), Some(PrimeFactor { factor: 2, exponent: 3 }));
+/// assert_eq!(pf.next(), Some(PrimeFactor { factor: 5, exponent: 1 }));
+/// assert_eq!(pf.next(), None);
+/// ```
+/// ```
+/// # use pj_euler::utils::primes::{PrimeFactorization, SpfTable};
+/// let spf = SpfTable::new(10_000);
+/// assert!((1..=10_000_u32).all(|n| spf.factorize(n).eq(PrimeFactorization::of(n))));
+/// ```
+pub struct SpfTable {
+    // The smallest prime factor of each index, or 0 for 0 and 1.
+    spf: Vec<u32>,
+
+    // The primes up to the bound, in ascending order.
+    primes: Vec<u32>,
+}
+
+impl SpfTable {
+    /// Build the table for every number up to and including `bound`.
+    #[must_use]
+    pub fn new(bound: usize) -> Self {
+        assert!(u32::try_from(bound).is_ok(), "bound fits in a u32");
+
+        let mut spf = vec![0; bound + 1];
+        let mut primes = vec![];
+        for i in 2..=bound {
+            if spf[i] == 0 {
+                spf[i] = i as u32;
+                primes.push(i as u32);
+            }
+
+            // every composite is marked exactly once, by its smallest prime factor
+            for &p in &primes {
+                if p > spf[i] || i * p as usize > bound {
+                    break;
+                }
+                spf[i * p as usize] = p;
+            }
+        }
+
+        Self { spf, primes }
+    }
+
+    /// The largest number in the table.
+    #[must_use]
+    pub fn bound(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    /// The primes up to the bound, in ascending order.
+    #[must_use]
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    /// The smallest prime factor of `n`, or `None` if `n` is 0 or 1.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than the bound.
+    #[must_use]
+    pub fn smallest_prime_factor(&self, n: usize) -> Option<u32> {
+        Some(self.spf[n]).filter(|&p| p != 0)
+    }
+
+    /// Factor `n` using the table.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than the bound.
+    pub fn factorize<N: Num + NumCast + Copy>(&self, n: N) -> PrimeFactorization<N> {
+        let mut n: usize = num::cast(n).expect("number fits in a usize");
+
+        let mut factors = vec![];
+        while let Some(p) = self.smallest_prime_factor(n) {
+            let mut exponent = 0;
+            while n.is_multiple_of(p as usize) {
+                n /= p as usize;
+                exponent += 1;
+            }
+            factors.push(PrimeFactor {
+                factor: num::cast(p).expect("factor is at most the original number"),
+                exponent: num::cast(exponent).expect("exponent is at most the original number"),
+            });
+        }
+
+        PrimeFactorization {
+            state: FactorizationState::Computed(factors.into_iter()),
+        }
+    }
+}
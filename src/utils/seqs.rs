@@ -1,79 +1,27 @@
 //! Iterators of various numeric sequences.
 
-use std::collections::HashMap;
-
-use num::{Num, NumCast};
-
-use super::num_fn::{_0, _1, _2, _3};
-
-/// An iterator over the Triangle numbers, defined by
-///
-/// ```text
-/// T(0) = 0
-/// T(n) = T(n-1) + n = n(n + 1)/2
-/// ```
-///
-/// We also implement `Iterator::nth` manually using the explicit formula. It gets the nth of the
-/// remaining sequence, not the nth from the beginning.
-///
-/// # Examples
-/// ```
-/// # use pj_euler::utils::seqs::Triangle;
-/// let mut t = Triangle::new();
-/// assert_eq!(t.next(), Some(0));
-/// assert_eq!(t.next(), Some(1));
-/// assert_eq!(t.next(), Some(3));
-/// assert_eq!(t.next(), Some(6));
-/// ```
-/// ```
-/// # use pj_euler::utils::seqs::Triangle;
-/// assert_eq!(Triangle::new().nth(0), Some(0));
-/// assert_eq!(Triangle::new().nth(1000), Some(500_500));
-/// ```
-pub struct Triangle<T> {
-    // The previously yielded triangle number.
-    last: T,
+mod collatz;
+mod figurate;
+mod iterated;
+mod palindromes;
+mod pythagorean;
+mod recurrence;
 
-    // The number of triangle numbers yielded so far.
-    count: T,
-}
-
-impl<N: Num> Default for Triangle<N> {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl<N: Num> Triangle<N> {
-    #[must_use]
-    pub fn new() -> Self {
-        Self {
-            last: _0(),
-            count: _0(),
-        }
-    }
-}
-
-impl<N: Num + NumCast + Copy> Iterator for Triangle<N> {
-    type Item = N;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.last = self.last + self.count;
-        self.count = self.count + _1();
-        Some(self.last)
-    }
+use std::collections::HashMap;
 
-    fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        // we are computing T(n)
-        let n = self.count + num::cast(n).unwrap();
+use num::Num;
 
-        // uses the fact that T(n) = n(n+1)/2
-        self.last = n * (n + _1()) / _2();
-        self.count = n + _1();
+pub use collatz::CollatzTable;
+pub use figurate::{
+    is_polygonal, polygonal_index, CenteredPolygonal, GeneralizedPentagonal, Heptagonal, Hexagonal,
+    Octagonal, Pentagonal, Polygonal, Square, Triangle,
+};
+pub use iterated::{Cycle, IteratedMap};
+pub use palindromes::{largest_palindrome_product, Palindromes};
+pub use pythagorean::{triple_counts_up_to, PythagoreanTriples};
+pub use recurrence::{Fibonacci, LinearRecurrence};
 
-        Some(self.last)
-    }
-}
+use super::num_fn::{_0, _1, _2, _3};
 
 /// An iterator over the Collatz sequence starting from n, defined by
 ///
@@ -148,6 +96,7 @@ impl<N: Num + Copy + PartialOrd> Iterator for Collatz<N> {
         Some(out)
     }
 }
+
 /// Adds the collatz length of n (and maybe other numbers) to the provided map.
 ///
 /// A lot of computation can easily be shared through this process.
@@ -183,7 +132,12 @@ pub fn collatz_length<
 
 #[cfg(test)]
 mod benches {
-    use super::Triangle;
+    use super::{
+        largest_palindrome_product, triple_counts_up_to, CollatzTable, Fibonacci, IteratedMap,
+        LinearRecurrence, Triangle,
+    };
+    use crate::utils::digits::Digits;
+    use crate::utils::modular::ModInt;
 
     extern crate test;
     use test::Bencher;
@@ -196,4 +150,64 @@ mod benches {
             assert_eq!(t.next(), Some(5_000_000_050_000_000 + 100_000_001));
         });
     }
+
+    #[bench]
+    fn largest_palindrome_product_eight_digits(b: &mut Bencher) {
+        b.iter(|| assert_eq!(largest_palindrome_product(8), 9_999_000_000_009_999));
+    }
+
+    #[bench]
+    fn fibonacci_nth(b: &mut Bencher) {
+        type Mod = ModInt<1_000_000_007>;
+        b.iter(|| {
+            let mut f = Fibonacci::<Mod>::new();
+            assert_eq!(
+                f.nth(1_000_000_000_000_000_000),
+                Some(Mod::new(209_783_453))
+            );
+        });
+    }
+
+    #[bench]
+    fn linear_recurrence_nth(b: &mut Bencher) {
+        type Mod = ModInt<1_000_000_007>;
+        b.iter(|| {
+            let mut f = LinearRecurrence::new(vec![Mod::new(1); 2], vec![Mod::new(0), Mod::new(1)]);
+            assert_eq!(
+                f.nth(1_000_000_000_000_000_000),
+                Some(Mod::new(209_783_453))
+            );
+        });
+    }
+
+    #[bench]
+    fn collatz_table_one_million(b: &mut Bencher) {
+        b.iter(|| {
+            let table = CollatzTable::new(1_000_000);
+            assert_eq!(table.get(837_799), Some(525));
+        });
+    }
+
+    #[bench]
+    fn factorial_digit_chains(b: &mut Bencher) {
+        const FACTORIALS: [u32; 10] = [1, 1, 2, 6, 24, 120, 720, 5040, 40320, 362_880];
+        b.iter(|| {
+            let sixty = (1..100_000)
+                .filter(|&n| {
+                    let step = |&n: &u32| Digits::of(n).map(|d| FACTORIALS[d as usize]).sum();
+                    let cycle = IteratedMap::new(n, step).cycle();
+                    cycle.tail + cycle.length == 60
+                })
+                .count();
+            assert_eq!(sixty, 42);
+        });
+    }
+
+    #[bench]
+    fn perimeters_with_one_triple(b: &mut Bencher) {
+        b.iter(|| {
+            let counts = triple_counts_up_to(1_500_000);
+            assert_eq!(counts.into_iter().filter(|&c| c == 1).count(), 161_667);
+        });
+    }
 }
@@ -0,0 +1,120 @@
+//! A table of Collatz sequence lengths.
+
+use std::collections::HashMap;
+
+/// The most lengths of numbers above the bound a [`CollatzTable`] remembers.
+const COLLATZ_OVERFLOW_CAPACITY: usize = 1 << 16;
+
+/// A table of Collatz lengths, counting the starting number and the final 1, as in
+/// [`collatz_length`](super::collatz_length).
+///
+/// Lengths below the bound are stored densely, and computed up front; lengths of larger numbers
+/// are computed on demand and remembered in a map, up to a fixed number of entries. Everything is
+/// iterative, so large bounds can't overflow the stack.
+///
+/// # Examples
+/// ```
+/// # use std::collections::HashMap;
+/// # use pj_euler::utils::seqs::{collatz_length, CollatzTable};
+/// let mut table = CollatzTable::new(1000);
+/// assert_eq!(table.get(17), Some(13));
+/// assert_eq!(table.get(1), Some(1));
+/// assert_eq!(table.get(1000), None);
+/// assert_eq!(table.bound(), 1000);
+/// assert_eq!(table.length(1000), 112);
+/// assert_eq!(table.length(837_799), 525);
+///
+/// let mut map = HashMap::new();
+/// assert!((1..5000).all(|n| usize::from(table.length(n)) == collatz_length(n, &mut map)));
+///
+/// // an empty table still computes lengths
+/// let mut empty = CollatzTable::new(0);
+/// assert_eq!((empty.bound(), empty.get(1)), (0, None));
+/// assert_eq!(empty.length(27), 112);
+/// ```
+pub struct CollatzTable {
+    // The bound asked for.
+    bound: usize,
+
+    // The length of each number below the bound, or 0 for 0, and always at least up to 1, where
+    // every sequence ends.
+    lengths: Vec<u16>,
+
+    // The lengths of some numbers at or above the bound.
+    overflow: HashMap<u64, u16>,
+
+    // Scratch space for the numbers on the path currently being followed.
+    path: Vec<u64>,
+}
+
+impl CollatzTable {
+    /// Build the table for every number below `bound`.
+    #[must_use]
+    pub fn new(bound: usize) -> Self {
+        let mut table = Self {
+            bound,
+            lengths: vec![0; bound.max(2)],
+            overflow: HashMap::new(),
+            path: vec![],
+        };
+        table.lengths[1] = 1;
+        for n in 2..bound {
+            if table.lengths[n] == 0 {
+                table.length(n as u64);
+            }
+        }
+        table
+    }
+
+    /// The number below which every length is stored.
+    #[must_use]
+    pub fn bound(&self) -> usize {
+        self.bound
+    }
+
+    /// The Collatz length of n, if n is in the table.
+    #[must_use]
+    pub fn get(&self, n: u64) -> Option<u16> {
+        if n >= self.bound as u64 {
+            return None;
+        }
+        self.lengths.get(n as usize).copied().filter(|&l| l > 0)
+    }
+
+    /// The Collatz length of n, computing and remembering it if n is above the bound.
+    ///
+    /// # Panics
+    /// Panics if n is 0.
+    pub fn length(&mut self, n: u64) -> u16 {
+        assert!(n > 0, "0 has no Collatz length");
+
+        // follow the sequence until a known length, taking (3n + 1)/2 as a single step
+        let mut m = n;
+        let mut known = loop {
+            let l = match self.lengths.get(m as usize) {
+                Some(&l) => l,
+                None => self.overflow.get(&m).copied().unwrap_or(0),
+            };
+            if l > 0 {
+                break l;
+            }
+            self.path.push(m);
+            m = if m.is_multiple_of(2) {
+                m / 2
+            } else {
+                (3 * m).div_ceil(2)
+            };
+        };
+
+        // then fill in the lengths along the way in reverse
+        while let Some(m) = self.path.pop() {
+            known += if m.is_multiple_of(2) { 1 } else { 2 };
+            if let Some(l) = self.lengths.get_mut(m as usize) {
+                *l = known;
+            } else if self.overflow.len() < COLLATZ_OVERFLOW_CAPACITY {
+                self.overflow.insert(m, known);
+            }
+        }
+        known
+    }
+}
@@ -0,0 +1,307 @@
+//! Figurate numbers: polygonal, centered polygonal and generalized pentagonal numbers.
+
+use num::Num;
+
+use crate::utils::num_fn::{_0, _1, _2, _3};
+
+/// An iterator over the `S`-gonal numbers, defined by
+///
+/// ```text
+/// P(0) = 0
+/// P(n) = P(n-1) + (S - 2)(n - 1) + 1 = (S - 2)n(n - 1)/2 + n
+/// ```
+///
+/// We also implement `Iterator::nth` manually using the explicit formula. It gets the nth of the
+/// remaining sequence, not the nth from the beginning.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::{Hexagonal, Pentagonal, Polygonal, Square};
+/// assert_eq!(Square::<u32>::new().take(5).collect::<Vec<_>>(), [0, 1, 4, 9, 16]);
+/// assert_eq!(Pentagonal::<u32>::new().take(5).collect::<Vec<_>>(), [0, 1, 5, 12, 22]);
+/// assert_eq!(Hexagonal::<u32>::new().take(5).collect::<Vec<_>>(), [0, 1, 6, 15, 28]);
+/// assert_eq!(Polygonal::<u32, 12>::new().take(5).collect::<Vec<_>>(), [0, 1, 12, 33, 64]);
+/// ```
+/// ```
+/// # use pj_euler::utils::seqs::{Heptagonal, Octagonal};
+/// assert_eq!(Octagonal::<u64>::new().nth(1000), Some(2_998_000));
+/// assert!((0..50).all(|n| Heptagonal::<u64>::new().nth(n) == Heptagonal::new().skip(n).next()));
+/// ```
+pub struct Polygonal<T, const S: usize> {
+    // The previously yielded polygonal number.
+    last: T,
+
+    // The number of polygonal numbers yielded so far.
+    count: T,
+}
+
+/// An iterator over the Triangle numbers, `T(n) = n(n + 1)/2`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::Triangle;
+/// let mut t = Triangle::new();
+/// assert_eq!(t.next(), Some(0));
+/// assert_eq!(t.next(), Some(1));
+/// assert_eq!(t.next(), Some(3));
+/// assert_eq!(t.next(), Some(6));
+/// ```
+/// ```
+/// # use pj_euler::utils::seqs::Triangle;
+/// assert_eq!(Triangle::new().nth(0), Some(0));
+/// assert_eq!(Triangle::new().nth(1000), Some(500_500));
+/// ```
+pub type Triangle<T> = Polygonal<T, 3>;
+
+/// An iterator over the squares.
+pub type Square<T> = Polygonal<T, 4>;
+
+/// An iterator over the pentagonal numbers, `n(3n - 1)/2`.
+pub type Pentagonal<T> = Polygonal<T, 5>;
+
+/// An iterator over the hexagonal numbers, `n(2n - 1)`.
+pub type Hexagonal<T> = Polygonal<T, 6>;
+
+/// An iterator over the heptagonal numbers, `n(5n - 3)/2`.
+pub type Heptagonal<T> = Polygonal<T, 7>;
+
+/// An iterator over the octagonal numbers, `n(3n - 2)`.
+pub type Octagonal<T> = Polygonal<T, 8>;
+
+impl<N: Num, const S: usize> Default for Polygonal<N, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Num, const S: usize> Polygonal<N, S> {
+    /// Make an iterator over the polygonal numbers, starting from 0.
+    ///
+    /// # Panics
+    /// Panics if `S` is less than 3.
+    #[must_use]
+    pub fn new() -> Self {
+        assert!(S >= 3, "polygons have at least 3 sides");
+        Self {
+            last: _0(),
+            count: _0(),
+        }
+    }
+}
+
+impl<N: Num + num::NumCast + Copy, const S: usize> Iterator for Polygonal<N, S> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s: N = num::cast(S).expect("S fits in N");
+        if self.count != _0() {
+            self.last = self.last + (s - _2()) * (self.count - _1()) + _1();
+        }
+        self.count = self.count + _1();
+        Some(self.last)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // we are computing P(n)
+        let n = self.count + num::cast(n).unwrap();
+        let s: N = num::cast(S).expect("S fits in N");
+
+        // n(n - 1)/2 is written as T(n) - n to avoid going below zero
+        self.last = (s - _2()) * (n * (n + _1()) / _2() - n) + n;
+        self.count = n + _1();
+
+        Some(self.last)
+    }
+}
+
+/// The index k with `P(k) = n` for the s-gonal numbers P, if n is s-gonal.
+///
+/// Solving the quadratic `(s - 2)k^2 - (s - 4)k - 2n = 0` gives
+///
+/// ```text
+/// k = ((s - 4) + sqrt((s - 4)^2 + 8(s - 2)n)) / (2(s - 2))
+/// ```
+///
+/// # Panics
+/// Panics if s is less than 3.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::polygonal_index;
+/// assert_eq!(polygonal_index(0, 5), Some(0));
+/// assert_eq!(polygonal_index(28, 3), Some(7));
+/// assert_eq!(polygonal_index(28, 6), Some(4));
+/// assert_eq!(polygonal_index(29, 3), None);
+///
+/// // the second s-gonal number is always s
+/// assert_eq!(polygonal_index(u64::MAX, u64::MAX), Some(2));
+/// assert_eq!(polygonal_index(u64::MAX - 1, u64::MAX), None);
+/// assert_eq!(polygonal_index(3 * (1 << 62) - 3, 1 << 62), Some(3));
+/// ```
+#[must_use]
+pub fn polygonal_index(n: u64, s: u64) -> Option<u64> {
+    assert!(s >= 3, "polygons have at least 3 sides");
+    if n == 0 {
+        return Some(0); // the other root of the quadratic
+    }
+    let (n, s) = (i128::from(n), i128::from(s));
+    let Some(discriminant) = (8 * (s - 2))
+        .checked_mul(n)
+        .and_then(|d| d.checked_add((s - 4).checked_pow(2)?))
+    else {
+        // only when s > 2^59, so (s - 2)k(k - 1)/2 <= n means k is at most 8
+        return (1..10)
+            .find(|&k| (s - 2) * k * (k - 1) / 2 + k == n)
+            .map(|k| k as u64);
+    };
+    let root = discriminant.isqrt();
+    if root * root != discriminant {
+        return None;
+    }
+    let numerator = root + s - 4;
+    (numerator % (2 * (s - 2)) == 0).then(|| (numerator / (2 * (s - 2))) as u64)
+}
+
+/// Check whether n is an s-gonal number.
+///
+/// # Panics
+/// Panics if s is less than 3.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::{is_polygonal, Hexagonal, Pentagonal, Triangle};
+/// assert!(is_polygonal(40_755, 3) && is_polygonal(40_755, 5) && is_polygonal(40_755, 6));
+///
+/// let pentagonal: Vec<u64> = Pentagonal::new().take_while(|&p| p < 10_000).collect();
+/// assert!((0..10_000).all(|n| is_polygonal(n, 5) == pentagonal.contains(&n)));
+///
+/// // the next number after 40755 that is triangular, pentagonal and hexagonal
+/// let next = Hexagonal::<u64>::new()
+///     .skip(144)
+///     .find(|&h| is_polygonal(h, 5))
+///     .unwrap();
+/// assert_eq!(next, 1_533_776_805);
+/// assert!(is_polygonal(next, 3));
+/// ```
+#[must_use]
+pub fn is_polygonal(n: u64, s: u64) -> bool {
+    polygonal_index(n, s).is_some()
+}
+
+/// An iterator over the centered `S`-gonal numbers, the numbers of dots in a central dot
+/// surrounded by k polygonal layers, defined by
+///
+/// ```text
+/// C(0) = 1
+/// C(n) = C(n-1) + Sn = Sn(n + 1)/2 + 1
+/// ```
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::CenteredPolygonal;
+/// let hexagonal = CenteredPolygonal::<u32, 6>::new();
+/// assert_eq!(hexagonal.take(5).collect::<Vec<_>>(), [1, 7, 19, 37, 61]);
+/// assert_eq!(CenteredPolygonal::<u64, 4>::new().nth(1000), Some(2_002_001));
+/// ```
+pub struct CenteredPolygonal<T, const S: usize> {
+    // The next centered polygonal number to yield.
+    next: T,
+
+    // The number of centered polygonal numbers yielded so far.
+    count: T,
+}
+
+impl<N: Num, const S: usize> Default for CenteredPolygonal<N, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Num, const S: usize> CenteredPolygonal<N, S> {
+    /// Make an iterator over the centered polygonal numbers, starting from 1.
+    ///
+    /// # Panics
+    /// Panics if `S` is less than 3.
+    #[must_use]
+    pub fn new() -> Self {
+        assert!(S >= 3, "polygons have at least 3 sides");
+        Self {
+            next: _1(),
+            count: _0(),
+        }
+    }
+}
+
+impl<N: Num + num::NumCast + Copy, const S: usize> Iterator for CenteredPolygonal<N, S> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s: N = num::cast(S).expect("S fits in N");
+        let out = self.next;
+        self.count = self.count + _1();
+        self.next = self.next + s * self.count;
+        Some(out)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // jump to C(n), then yield it as usual
+        let n = self.count + num::cast(n).unwrap();
+        let s: N = num::cast(S).expect("S fits in N");
+        self.next = s * n * (n + _1()) / _2() + _1();
+        self.count = n;
+        self.next()
+    }
+}
+
+/// An iterator over the generalized pentagonal numbers `k(3k - 1)/2` for `k = 0, 1, -1, 2, -2,
+/// ...`, which are the exponents in Euler's pentagonal number theorem.
+///
+/// We implement `Iterator::nth` manually using the explicit formula, like [`Polygonal`].
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::GeneralizedPentagonal;
+/// let g = GeneralizedPentagonal::<u32>::new();
+/// assert_eq!(g.take(10).collect::<Vec<_>>(), [0, 1, 2, 5, 7, 12, 15, 22, 26, 35]);
+/// assert_eq!(GeneralizedPentagonal::<u64>::new().nth(2001), Some(1_502_501));
+/// ```
+pub struct GeneralizedPentagonal<T> {
+    // The number of generalized pentagonal numbers yielded so far.
+    count: T,
+}
+
+impl<N: Num> Default for GeneralizedPentagonal<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Num> GeneralizedPentagonal<N> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { count: _0() }
+    }
+}
+
+impl<N: Num + num::NumCast + Copy> Iterator for GeneralizedPentagonal<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the ith term has k = (i + 1) / 2, negated when i is even
+        let i = self.count;
+        let k = (i + _1()) / _2();
+        let three = _3::<N>();
+        let out = if i % _2() == _0() {
+            k * (three * k + _1()) / _2()
+        } else {
+            k * (three * k - _1()) / _2()
+        };
+        self.count = i + _1();
+        Some(out)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.count = self.count + num::cast(n).unwrap();
+        self.next()
+    }
+}
@@ -0,0 +1,146 @@
+//! Sequences made by iterating a function, and their cycles.
+
+/// An iterator over `x, f(x), f(f(x)), ...` for any step function f.
+///
+/// The sequence never ends by itself. Over a finite set of values it must eventually cycle, and
+/// [`IteratedMap::cycle`] finds where.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::{Collatz, IteratedMap};
+/// // the Collatz sequence, and its 3n - 1 variant
+/// let collatz = IteratedMap::new(6_u32, |&n| if n % 2 == 0 { n / 2 } else { 3 * n + 1 });
+/// assert!(collatz.take(20).eq(Collatz::of(6).take(20)));
+///
+/// let variant = IteratedMap::new(5_i32, |&n| if n % 2 == 0 { n / 2 } else { 3 * n - 1 });
+/// assert_eq!(variant.take(7).collect::<Vec<_>>(), [5, 14, 7, 20, 10, 5, 14]);
+///
+/// // the step is only applied when the next value is asked for
+/// let counter = IteratedMap::new(254_u8, |&n| n + 1);
+/// assert_eq!(counter.take(2).collect::<Vec<_>>(), [254, 255]);
+/// ```
+pub struct IteratedMap<T, F> {
+    // The last value yielded, or the start if nothing has been yet.
+    current: T,
+
+    // Whether `current` has been yielded, so that the next value is its step.
+    yielded: bool,
+
+    step: F,
+}
+
+/// Where an iterated map starts cycling.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<T> {
+    /// The number of values before the cycle.
+    pub tail: usize,
+
+    /// The number of values in the cycle.
+    pub length: usize,
+
+    /// The first value in the cycle.
+    pub entry: T,
+}
+
+impl<T, F: FnMut(&T) -> T> IteratedMap<T, F> {
+    /// Make the sequence starting at `start` and applying `step` to get each next value.
+    pub const fn new(start: T, step: F) -> Self {
+        Self {
+            current: start,
+            yielded: false,
+            step,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq, F: FnMut(&T) -> T> IteratedMap<T, F> {
+    /// Find the cycle the sequence ends up in, by Brent's algorithm.
+    ///
+    /// This takes `O(tail + length)` steps and keeps only a couple of values at a time. It never
+    /// returns if the sequence doesn't cycle.
+    ///
+    /// # Examples
+    /// ```
+    /// # use pj_euler::utils::digits::Digits;
+    /// # use pj_euler::utils::seqs::{Cycle, IteratedMap};
+    /// // 27 takes 111 steps to reach 1, and enters the cycle 4 -> 2 -> 1 two steps before that
+    /// let collatz = |&n: &u64| if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+    /// let cycle = IteratedMap::new(27, collatz).cycle();
+    /// assert_eq!(cycle, Cycle { tail: 109, length: 3, entry: 4 });
+    ///
+    /// // happy numbers end at the fixed point 1, and unhappy ones in a cycle of length 8
+    /// let square_digits = |&n: &u32| Digits::of(n).map(|d| d * d).sum();
+    /// let happy: Vec<_> = (1..50)
+    ///     .filter(|&n| IteratedMap::new(n, square_digits).cycle().entry == 1)
+    ///     .collect();
+    /// assert_eq!(happy, [1, 7, 10, 13, 19, 23, 28, 31, 32, 44, 49]);
+    /// assert_eq!(IteratedMap::new(4, square_digits).cycle().length, 8);
+    /// ```
+    /// ```
+    /// # use pj_euler::utils::digits::Digits;
+    /// # use pj_euler::utils::seqs::IteratedMap;
+    /// // chains of sums of factorials of digits, which all have at most 60 distinct terms
+    /// const FACTORIALS: [u32; 10] = [1, 1, 2, 6, 24, 120, 720, 5040, 40320, 362_880];
+    /// let factorial_digits = |&n: &u32| Digits::of(n).map(|d| FACTORIALS[d as usize]).sum();
+    /// let terms = |n| {
+    ///     let cycle = IteratedMap::new(n, factorial_digits).cycle();
+    ///     cycle.tail + cycle.length
+    /// };
+    /// assert_eq!(terms(69), 5); // 69 -> 363600 -> 1454 -> 169 -> 363601 -> 1454
+    /// assert_eq!(terms(145), 1);
+    /// assert_eq!(terms(871), 2);
+    /// assert!((1..1000).all(|n| terms(n) <= 60));
+    /// ```
+    pub fn cycle(mut self) -> Cycle<T> {
+        let start = if self.yielded {
+            (self.step)(&self.current)
+        } else {
+            self.current.clone()
+        };
+
+        // find the length by moving the hare ahead of the tortoise, teleporting the tortoise to
+        // the hare whenever the number of steps reaches a power of two
+        let (mut power, mut length) = (1, 1);
+        let mut tortoise = start.clone();
+        let mut hare = (self.step)(&start);
+        while tortoise != hare {
+            if power == length {
+                tortoise = hare.clone();
+                power *= 2;
+                length = 0;
+            }
+            hare = (self.step)(&hare);
+            length += 1;
+        }
+
+        // then two pointers a cycle length apart first meet at the start of the cycle
+        let (mut tortoise, mut hare) = (start.clone(), start);
+        for _ in 0..length {
+            hare = (self.step)(&hare);
+        }
+        let mut tail = 0;
+        while tortoise != hare {
+            tortoise = (self.step)(&tortoise);
+            hare = (self.step)(&hare);
+            tail += 1;
+        }
+
+        Cycle {
+            tail,
+            length,
+            entry: tortoise,
+        }
+    }
+}
+
+impl<T: Clone, F: FnMut(&T) -> T> Iterator for IteratedMap<T, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.yielded {
+            self.current = (self.step)(&self.current);
+        }
+        self.yielded = true;
+        Some(self.current.clone())
+    }
+}
@@ -0,0 +1,132 @@
+//! Palindromic numbers, which read the same forwards and backwards.
+
+use num::Num;
+
+use crate::utils::digits::reverse_number;
+use crate::utils::num_fn::{_0, _1};
+
+/// An iterator over the palindromes with exactly `len` digits in the given base, in ascending
+/// order; use `.rev()` for descending order.
+///
+/// Each palindrome is determined by its first `ceil(len / 2)` digits, so we iterate over those and
+/// mirror them.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::Palindromes;
+/// assert_eq!(Palindromes::of_length(1, 10).collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// assert_eq!(Palindromes::of_length(3, 10).take(4).collect::<Vec<_>>(), [101, 111, 121, 131]);
+/// assert_eq!(Palindromes::of_length(4, 10).rev().take(3).collect::<Vec<_>>(), [9999, 9889, 9779]);
+/// assert_eq!(Palindromes::of_length(4, 2).collect::<Vec<_>>(), [0b1001, 0b1111]);
+/// assert_eq!(Palindromes::<u64>::of_length(6, 10).count(), 900);
+/// ```
+/// ```
+/// # use pj_euler::utils::digits::is_palindrome_in_base;
+/// # use pj_euler::utils::seqs::Palindromes;
+/// let palindromes = (1..=4).flat_map(|len| Palindromes::of_length(len, 10));
+/// assert!(palindromes.eq((1..10_000).filter(|&n| is_palindrome_in_base(n, 10))));
+/// ```
+pub struct Palindromes<T> {
+    // The next first halves to mirror from the front and the back, inclusive.
+    front: T,
+    back: T,
+
+    base: T,
+
+    // base^(len / 2), the place value of the last digit of the first half.
+    shift: T,
+
+    // Whether the middle digit is shared between the two halves.
+    odd: bool,
+}
+
+impl<N: Num + PartialOrd + Copy> Palindromes<N> {
+    /// Make an iterator over the palindromes with `len` digits in the given base.
+    ///
+    /// # Panics
+    /// Panics if the base is less than 2.
+    #[must_use]
+    pub fn of_length(len: usize, base: N) -> Self {
+        assert!(base > _1(), "base is at least 2");
+        let pow = |k: usize| (0..k).fold(_1(), |p: N, _| p * base);
+        let (front, back) = if len == 0 {
+            (_1(), _0())
+        } else {
+            (pow(len.div_ceil(2) - 1), pow(len.div_ceil(2)) - _1())
+        };
+        Self {
+            front,
+            back,
+            base,
+            shift: pow(len / 2),
+            odd: len % 2 == 1,
+        }
+    }
+
+    /// The palindrome with the given first half.
+    fn mirror(&self, half: N) -> N {
+        let tail = if self.odd { half / self.base } else { half };
+        half * self.shift + reverse_number(tail, self.base)
+    }
+}
+
+impl<N: Num + PartialOrd + Copy> Iterator for Palindromes<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front > self.back {
+            return None;
+        }
+        let out = self.mirror(self.front);
+        self.front = self.front + _1();
+        Some(out)
+    }
+}
+
+impl<N: Num + PartialOrd + Copy> DoubleEndedIterator for Palindromes<N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front > self.back {
+            return None;
+        }
+        let out = self.mirror(self.back);
+        self.back = self.back - _1();
+        Some(out)
+    }
+}
+
+/// The largest palindrome which is a product of two numbers with the given number of decimal
+/// digits.
+///
+/// We go through palindromes in descending order and look for a factor whose cofactor also has
+/// the right number of digits. A palindrome with an even number of digits is divisible by 11,
+/// since its digits pair up into multiples of `10^i + 10^j` with `i + j` odd, so one of the
+/// factors must be too.
+///
+/// # Panics
+/// Panics unless `1 <= digits <= 9`, beyond which the products don't fit in a u64.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::largest_palindrome_product;
+/// assert_eq!(largest_palindrome_product(1), 9);
+/// assert_eq!(largest_palindrome_product(2), 9009);
+/// assert_eq!(largest_palindrome_product(4), 99_000_099);
+/// assert_eq!(largest_palindrome_product(7), 99_956_644_665_999);
+/// ```
+#[must_use]
+pub fn largest_palindrome_product(digits: u32) -> u64 {
+    assert!((1..=9).contains(&digits), "digits is between 1 and 9");
+    let (lo, hi) = (10_u64.pow(digits - 1), 10_u64.pow(digits) - 1);
+    (2 * digits - 1..=2 * digits)
+        .rev()
+        .flat_map(|len| Palindromes::of_length(len as usize, 10_u64).rev())
+        .find(|&p| {
+            let step = if p.ilog10() % 2 == 1 { 11 } else { 1 };
+            let top = hi.min(p / lo);
+            (lo.max(p.div_ceil(hi))..=top - top % step)
+                .rev()
+                .step_by(step as usize)
+                .any(|a| p.is_multiple_of(a))
+        })
+        .expect("the square of the largest number with the given digits is a product")
+}
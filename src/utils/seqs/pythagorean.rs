@@ -0,0 +1,176 @@
+//! Pythagorean triples, generated by the Berggren tree.
+
+use num::Num;
+
+use crate::utils::num_fn::{_1, _2, _3};
+
+/// The largest triples to generate.
+#[derive(Clone, Copy)]
+enum Bound<T> {
+    Perimeter(T),
+    Hypotenuse(T),
+}
+
+impl<N: Num + PartialOrd + Copy> Bound<N> {
+    fn admits(self, (a, b, c): (N, N, N)) -> bool {
+        match self {
+            Self::Perimeter(p) => a + b + c <= p,
+            Self::Hypotenuse(h) => c <= h,
+        }
+    }
+}
+
+/// An iterator over the Pythagorean triples `(a, b, c)` with `a < b < c` and `a^2 + b^2 = c^2`, up
+/// to a bound on the perimeter or hypotenuse, in no particular order.
+///
+/// Every primitive triple, where a, b and c are coprime, appears exactly once in the Berggren
+/// tree rooted at `(3, 4, 5)`, whose three children of `(a, b, c)` are
+///
+/// ```text
+/// (a - 2b + 2c, 2a - b + 2c, 2a - 2b + 3c)
+/// (a + 2b + 2c, 2a + b + 2c, 2a + 2b + 3c)
+/// (-a + 2b + 2c, -2a + b + 2c, -2a + 2b + 3c)
+/// ```
+///
+/// Children have larger perimeters and hypotenuses than their parents, so we can walk the tree
+/// depth first and prune it at the bound.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::PythagoreanTriples;
+/// let mut primitive: Vec<_> = PythagoreanTriples::with_hypotenuse_at_most(30_u32).collect();
+/// primitive.sort_by_key(|&(_, _, c)| c);
+/// assert_eq!(primitive, [(3, 4, 5), (5, 12, 13), (8, 15, 17), (7, 24, 25), (20, 21, 29)]);
+///
+/// let mut all: Vec<_> = PythagoreanTriples::with_perimeter_at_most(40_u32)
+///     .with_multiples()
+///     .collect();
+/// all.sort_unstable();
+/// assert_eq!(all, [(3, 4, 5), (5, 12, 13), (6, 8, 10), (8, 15, 17), (9, 12, 15)]);
+/// ```
+/// ```
+/// # use pj_euler::utils::seqs::PythagoreanTriples;
+/// let brute_force = (1..100_u32)
+///     .flat_map(|c| (1..c).flat_map(move |b| (1..b).map(move |a| (a, b, c))))
+///     .filter(|&(a, b, c)| a * a + b * b == c * c)
+///     .count();
+/// let triples = PythagoreanTriples::with_hypotenuse_at_most(99).with_multiples();
+/// assert_eq!(triples.count(), brute_force);
+/// ```
+pub struct PythagoreanTriples<T> {
+    // The primitive triples still to visit, as they come out of the tree.
+    stack: Vec<(T, T, T)>,
+
+    bound: Bound<T>,
+
+    // The last primitive triple and its next multiple, when yielding multiples.
+    multiple: Option<((T, T, T), T)>,
+
+    with_multiples: bool,
+}
+
+impl<N: Num + PartialOrd + Copy> PythagoreanTriples<N> {
+    fn new(bound: Bound<N>) -> Self {
+        let (three, four) = (_3::<N>(), _2::<N>() + _2());
+        let root = (three, four, four + _1());
+        Self {
+            stack: if bound.admits(root) {
+                vec![root]
+            } else {
+                vec![]
+            },
+            bound,
+            multiple: None,
+            with_multiples: false,
+        }
+    }
+
+    /// Make an iterator over the primitive triples with `a + b + c <= perimeter`.
+    #[must_use]
+    pub fn with_perimeter_at_most(perimeter: N) -> Self {
+        Self::new(Bound::Perimeter(perimeter))
+    }
+
+    /// Make an iterator over the primitive triples with `c <= hypotenuse`.
+    #[must_use]
+    pub fn with_hypotenuse_at_most(hypotenuse: N) -> Self {
+        Self::new(Bound::Hypotenuse(hypotenuse))
+    }
+
+    /// Also yield every multiple of each primitive triple within the bound, right after it.
+    #[must_use]
+    pub fn with_multiples(self) -> Self {
+        Self {
+            with_multiples: true,
+            ..self
+        }
+    }
+}
+
+impl<N: Num + PartialOrd + Copy> Iterator for PythagoreanTriples<N> {
+    type Item = (N, N, N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(((a, b, c), k)) = self.multiple {
+            let triple = (a * k, b * k, c * k);
+            if self.bound.admits(triple) {
+                self.multiple = Some(((a, b, c), k + _1()));
+                return Some(triple);
+            }
+            self.multiple = None;
+        }
+
+        let (a, b, c) = self.stack.pop()?;
+        let (two, three) = (_2::<N>(), _3::<N>());
+        // the negative terms are always outweighed, so add before subtracting
+        let children = [
+            (
+                a + two * c - two * b,
+                two * a + two * c - b,
+                two * a + three * c - two * b,
+            ),
+            (
+                a + two * b + two * c,
+                two * a + b + two * c,
+                two * a + two * b + three * c,
+            ),
+            (
+                two * b + two * c - a,
+                b + two * c - two * a,
+                two * b + three * c - two * a,
+            ),
+        ];
+        self.stack.extend(
+            children
+                .into_iter()
+                .filter(|&child| self.bound.admits(child)),
+        );
+
+        let triple = if a < b { (a, b, c) } else { (b, a, c) };
+        if self.with_multiples {
+            self.multiple = Some((triple, two));
+        }
+        Some(triple)
+    }
+}
+
+/// The number of Pythagorean triples, primitive or not, with each perimeter up to n.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::triple_counts_up_to;
+/// let counts = triple_counts_up_to(1000);
+/// assert_eq!(counts[12], 1);
+/// assert_eq!(counts[120], 3); // (30, 40, 50), (20, 48, 52), (24, 45, 51)
+/// assert_eq!((0..=1000).max_by_key(|&p| counts[p]), Some(840));
+/// ```
+#[must_use]
+pub fn triple_counts_up_to(n: usize) -> Vec<usize> {
+    let mut counts = vec![0; n + 1];
+    for (a, b, c) in PythagoreanTriples::with_perimeter_at_most(n) {
+        for p in (a + b + c..=n).step_by(a + b + c) {
+            counts[p] += 1;
+        }
+    }
+    counts
+}
@@ -0,0 +1,289 @@
+//! Sequences defined by linear recurrences.
+
+use std::collections::VecDeque;
+
+use num::Num;
+
+use crate::utils::num_fn::{_0, _1, _2};
+
+/// An iterator over the Fibonacci numbers, defined by
+///
+/// ```text
+/// F(0) = 0
+/// F(1) = 1
+/// F(n) = F(n-1) + F(n-2)
+/// ```
+///
+/// Like [`Polygonal`](super::Polygonal), we implement `Iterator::nth` manually, here by fast
+/// doubling in `O(log n)` arithmetic operations. It gets the nth of the remaining sequence, not
+/// the nth from the beginning.
+///
+/// Only `Clone` is needed, so this works with `BigUint` as well as primitives and `ModInt`.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::Fibonacci;
+/// let mut f = Fibonacci::<u32>::new();
+/// assert_eq!(f.by_ref().take(8).collect::<Vec<_>>(), [0, 1, 1, 2, 3, 5, 8, 13]);
+/// assert_eq!(f.nth(2), Some(55));
+/// assert_eq!(f.next(), Some(89));
+/// ```
+/// ```
+/// # use pj_euler::utils::seqs::Fibonacci;
+/// assert_eq!(Fibonacci::<u64>::new().nth(90), Some(2_880_067_194_370_816_120));
+///
+/// // the largest Fibonacci numbers that fit
+/// assert_eq!(Fibonacci::<u64>::new().nth(92), Some(7_540_113_804_746_346_429));
+/// assert_eq!(Fibonacci::<u64>::new().nth(93), Some(12_200_160_415_121_876_738));
+/// assert_eq!(Fibonacci::<u64>::new().take(94).last(), Some(12_200_160_415_121_876_738));
+/// assert!((0..50).all(|n| Fibonacci::<u64>::new().nth(n) == Fibonacci::new().skip(n).next()));
+/// ```
+/// ```
+/// # use num::BigUint;
+/// # use pj_euler::utils::bignum::num_digits;
+/// # use pj_euler::utils::modular::ModInt;
+/// # use pj_euler::utils::seqs::Fibonacci;
+/// // the first Fibonacci number with 1000 digits
+/// assert_eq!(num_digits(&Fibonacci::<BigUint>::new().nth(4781).unwrap()), 999);
+/// assert_eq!(num_digits(&Fibonacci::<BigUint>::new().nth(4782).unwrap()), 1000);
+///
+/// // the Pisano period of 10^9 is 1.5 * 10^9
+/// type Mod = ModInt<1_000_000_000>;
+/// assert_eq!(Fibonacci::<Mod>::new().nth(1_500_000_000), Some(Mod::new(0)));
+/// assert_eq!(Fibonacci::<Mod>::new().nth(1_500_000_001), Some(Mod::new(1)));
+/// ```
+pub struct Fibonacci<T> {
+    // F(k-1) and F(k) for k the index of the last term yielded, starting from F(-1) = 1 and
+    // F(0) = 0. The next term is only added up when it's asked for, so this never overflows
+    // before the terms themselves do.
+    prev: T,
+    curr: T,
+
+    // The index of the next term to yield.
+    index: usize,
+}
+
+impl<N: Num> Default for Fibonacci<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Num> Fibonacci<N> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            prev: _1(),
+            curr: _0(),
+            index: 0,
+        }
+    }
+}
+
+impl<N: Num + Clone> Iterator for Fibonacci<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index > 0 {
+            let after = self.prev.clone() + self.curr.clone();
+            self.prev = std::mem::replace(&mut self.curr, after);
+        }
+        self.index += 1;
+        Some(self.curr.clone())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // (F(k-1), F(k)) for k the prefix of the bits of m read so far, using
+        //   F(2k-1) = F(k-1)^2 + F(k)^2
+        //   F(2k) = F(k) (2 F(k-1) + F(k))
+        // so nothing past F(m) is ever computed
+        let m = self.index + n;
+        let (mut a, mut b) = (_1::<N>(), _0::<N>());
+        for bit in (0..usize::BITS - m.leading_zeros()).rev() {
+            let odd = a.clone() * a.clone() + b.clone() * b.clone();
+            let even = b.clone() * (a * _2() + b);
+            (a, b) = if (m >> bit) & 1 == 1 {
+                (even.clone(), even + odd)
+            } else {
+                (odd, even)
+            };
+        }
+
+        (self.prev, self.curr) = (a, b);
+        self.index = m + 1;
+        Some(self.curr.clone())
+    }
+}
+
+/// An iterator over a sequence satisfying a linear recurrence with constant coefficients,
+///
+/// ```text
+/// a(n) = c[0] a(n-1) + c[1] a(n-2) + ... + c[d-1] a(n-d)
+/// ```
+///
+/// starting from d given initial terms.
+///
+/// `Iterator::nth` is implemented with Kitamasa's method: `a(k + n)` is a combination of the d
+/// terms from `a(k)`, with coefficients given by `x^n` modulo the characteristic polynomial
+/// `x^d - c[0] x^(d-1) - ... - c[d-1]`. That takes `O(d^2 log n)` arithmetic operations, and only
+/// needs addition and multiplication, so it works with `BigUint` and `ModInt` alike.
+///
+/// # Examples
+/// ```
+/// # use pj_euler::utils::seqs::{Fibonacci, LinearRecurrence};
+/// let fib = LinearRecurrence::<u64>::new(vec![1, 1], vec![0, 1]);
+/// assert!(fib.take(94).eq(Fibonacci::new().take(94)));
+/// let mut fib = LinearRecurrence::<u64>::new(vec![1, 1], vec![0, 1]);
+/// assert_eq!(fib.nth(93), Some(12_200_160_415_121_876_738));
+///
+/// // tribonacci
+/// let mut t = LinearRecurrence::<u64>::new(vec![1, 1, 1], vec![0, 0, 1]);
+/// assert_eq!(t.by_ref().take(10).collect::<Vec<_>>(), [0, 0, 1, 1, 2, 4, 7, 13, 24, 44]);
+/// assert_eq!(t.nth(27), Some(1_132_436_852));
+/// assert_eq!(t.next(), Some(2_082_876_103));
+/// ```
+/// ```
+/// # use pj_euler::utils::modular::ModInt;
+/// # use pj_euler::utils::seqs::LinearRecurrence;
+/// type Mod = ModInt<1_000_000_007>;
+///
+/// // a(n) = 2 a(n-1) is just the powers of 2
+/// let mut powers = LinearRecurrence::new(vec![Mod::new(2)], vec![Mod::new(1)]);
+/// assert_eq!(powers.nth(1_000_000_000_000), Some(Mod::new(2).pow(1_000_000_000_000)));
+///
+/// // nth agrees with stepping one term at a time
+/// let rec = || {
+///     let coefficients = [3, 0, 5].map(Mod::new).to_vec();
+///     LinearRecurrence::new(coefficients, vec![Mod::new(1); 3])
+/// };
+/// let terms: Vec<_> = rec().take(100).collect();
+/// assert!((0..100).all(|n| rec().nth(n) == Some(terms[n])));
+/// ```
+pub struct LinearRecurrence<T> {
+    coefficients: Vec<T>,
+
+    // The last d terms computed, starting with the initial terms. New terms are only computed
+    // when they're asked for, so this never overflows before the terms themselves do.
+    window: VecDeque<T>,
+
+    // The number of terms at the end of the window which haven't been yielded yet.
+    pending: usize,
+}
+
+impl<N: Num + Clone> LinearRecurrence<N> {
+    /// Make the sequence with the given recurrence coefficients, `c[0]` being the coefficient of
+    /// the previous term, and initial terms.
+    ///
+    /// # Panics
+    /// Panics if there are no coefficients, or a different number of coefficients and initial
+    /// terms.
+    #[must_use]
+    pub fn new(coefficients: Vec<N>, initial: Vec<N>) -> Self {
+        assert!(!coefficients.is_empty(), "there are coefficients");
+        assert!(
+            coefficients.len() == initial.len(),
+            "there is an initial term for each coefficient"
+        );
+        Self {
+            pending: initial.len(),
+            coefficients,
+            window: initial.into(),
+        }
+    }
+
+    /// The polynomial `x * r(x)` modulo the characteristic polynomial, where r is already reduced.
+    fn mul_x(&self, r: &[N]) -> Vec<N> {
+        let d = r.len();
+        let top = r[d - 1].clone();
+        let mut out: Vec<N> = std::iter::once(_0())
+            .chain(r[..d - 1].iter().cloned())
+            .collect();
+        for (j, c) in self.coefficients.iter().enumerate() {
+            // x^d = c[0] x^(d-1) + ... + c[d-1]
+            out[d - 1 - j] = out[d - 1 - j].clone() + top.clone() * c.clone();
+        }
+        out
+    }
+
+    /// The product of two reduced polynomials, modulo the characteristic polynomial.
+    fn mul(&self, a: &[N], b: &[N]) -> Vec<N> {
+        let d = a.len();
+        let mut product = vec![_0::<N>(); 2 * d - 1];
+        for (i, a) in a.iter().enumerate() {
+            for (j, b) in b.iter().enumerate() {
+                product[i + j] = product[i + j].clone() + a.clone() * b.clone();
+            }
+        }
+
+        // reduce from the top, replacing each x^k by x^(k-d) times the lower terms of x^d
+        for k in (d..2 * d - 1).rev() {
+            let top = std::mem::replace(&mut product[k], _0());
+            for (j, c) in self.coefficients.iter().enumerate() {
+                product[k - 1 - j] = product[k - 1 - j].clone() + top.clone() * c.clone();
+            }
+        }
+        product.truncate(d);
+        product
+    }
+}
+
+impl<N: Num + Clone> Iterator for LinearRecurrence<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending == 0 {
+            let after = self
+                .coefficients
+                .iter()
+                .zip(self.window.iter().rev())
+                .fold(_0::<N>(), |sum, (c, a)| sum + c.clone() * a.clone());
+            self.window.pop_front();
+            self.window.push_back(after);
+            self.pending = 1;
+        }
+        let d = self.window.len();
+        self.pending -= 1;
+        Some(self.window[d - 1 - self.pending].clone())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.pending {
+            self.pending -= n;
+            return self.next();
+        }
+
+        // with the window a(s), ..., a(s + d - 1), the new window ends at the term asked for
+        let d = self.coefficients.len();
+        let mut shift = n - self.pending + 1;
+
+        // x^shift modulo the characteristic polynomial, by square-and-multiply
+        let unit: Vec<N> = (0..d).map(|i| if i == 0 { _1() } else { _0() }).collect();
+        let (mut r, mut base) = (unit.clone(), self.mul_x(&unit));
+        loop {
+            if shift & 1 == 1 {
+                r = self.mul(&r, &base);
+            }
+            shift >>= 1;
+            if shift == 0 {
+                break;
+            }
+            base = self.mul(&base, &base);
+        }
+
+        // a(s + shift + i) is the combination of the window with coefficients x^(shift + i)
+        let mut window = VecDeque::with_capacity(d);
+        for i in 0..d {
+            if i > 0 {
+                r = self.mul_x(&r);
+            }
+            let term = r
+                .iter()
+                .zip(&self.window)
+                .fold(_0::<N>(), |sum, (r, a)| sum + r.clone() * a.clone());
+            window.push_back(term);
+        }
+        self.window = window;
+        self.pending = 0;
+        self.window.back().cloned()
+    }
+}